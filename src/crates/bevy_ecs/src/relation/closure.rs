@@ -0,0 +1,405 @@
+use super::*;
+use crate::{
+    change_detection::{DetectChangesMut, Mut},
+    query::ReadOnlyWorldQuery,
+    system::Resource,
+};
+use std::collections::VecDeque;
+
+// Transitive reachability over a relation, following edges from foster to target.
+// `is_reachable` and `transitive_targets` walk `Edges` on every call. Relations that are queried
+// often can be tracked in `Closures` instead which keeps the transitive targets of every foster.
+// Additions are propagated in place while removals and despawns recompute affected entries.
+
+fn targets_erased(edges: &Edges, relation: TypeId) -> impl '_ + Iterator<Item = Entity> {
+    edges
+        .targets
+        .iter()
+        .filter_map(move |targets| targets.get(&relation))
+        .flat_map(|targets| targets.keys().copied())
+}
+
+// Breadth first walk of everything reachable from `from`. Stops early if `visit` returns true.
+fn reach<'a>(
+    get_edges: impl Fn(Entity) -> Option<&'a Edges>,
+    relation: TypeId,
+    from: Entity,
+    mut visit: impl FnMut(Entity) -> bool,
+) {
+    let mut visited = HashSet::<Entity>::new();
+    let mut queue = VecDeque::from([from]);
+
+    while let Some(entity) = queue.pop_front() {
+        let Some(edges) = get_edges(entity) else {
+            continue;
+        };
+
+        for target in targets_erased(edges, relation) {
+            if get_edges(target).is_none() || !visited.insert(target) {
+                continue;
+            }
+
+            if visit(target) {
+                return;
+            }
+
+            queue.push_back(target);
+        }
+    }
+}
+
+fn is_reachable<'a>(
+    get_edges: impl Fn(Entity) -> Option<&'a Edges>,
+    relation: TypeId,
+    from: Entity,
+    to: Entity,
+) -> bool {
+    let mut found = false;
+    reach(get_edges, relation, from, |entity| {
+        found = entity == to;
+        found
+    });
+    found
+}
+
+fn transitive_targets<'a>(
+    get_edges: impl Fn(Entity) -> Option<&'a Edges>,
+    relation: TypeId,
+    from: Entity,
+) -> Vec<Entity> {
+    let mut targets = Vec::new();
+    reach(get_edges, relation, from, |entity| {
+        targets.push(entity);
+        false
+    });
+    targets
+}
+
+impl World {
+    /// Returns true if `to` can be reached from `from` by following `R` edges.
+    pub fn is_reachable<R: Relation>(&self, from: Entity, to: Entity) -> bool {
+        is_reachable(
+            |e| self.get::<Edges>(e),
+            TypeId::of::<Storage<R>>(),
+            from,
+            to,
+        )
+    }
+
+    /// Returns every entity that can be reached from `from` by following `R` edges in
+    /// breadth first order.
+    pub fn transitive_targets<R: Relation>(&self, from: Entity) -> Vec<Entity> {
+        transitive_targets(|e| self.get::<Edges>(e), TypeId::of::<Storage<R>>(), from)
+    }
+
+    /// Starts maintaining the transitive closure of `R` in the [`Closures`] resource.
+    pub fn track_closure<R: Relation>(&mut self) {
+        let relation = TypeId::of::<Storage<R>>();
+
+        let fosters = self
+            .query::<(Entity, &Edges)>()
+            .iter(self)
+            .filter(|(_, edges)| targets_erased(edges, relation).next().is_some())
+            .map(|(entity, _)| entity)
+            .collect::<Vec<_>>();
+
+        let closure = fosters
+            .into_iter()
+            .map(|foster| {
+                let targets = transitive_targets(|e| self.get::<Edges>(e), relation, foster);
+                (foster, targets.into_iter().collect())
+            })
+            .collect();
+
+        self.get_resource_or_insert_with(Closures::default)
+            .relations
+            .insert(relation, closure);
+    }
+}
+
+impl<F: ReadOnlyWorldQuery> Query<'_, '_, &Edges, F> {
+    /// Returns true if `to` can be reached from `from` by following `R` edges.
+    /// Only entities matched by this query are walked.
+    pub fn is_reachable<R: Relation>(&self, from: Entity, to: Entity) -> bool {
+        is_reachable(|e| self.get(e).ok(), TypeId::of::<Storage<R>>(), from, to)
+    }
+
+    /// Returns every entity that can be reached from `from` by following `R` edges in
+    /// breadth first order. Only entities matched by this query are walked.
+    pub fn transitive_targets<R: Relation>(&self, from: Entity) -> Vec<Entity> {
+        transitive_targets(|e| self.get(e).ok(), TypeId::of::<Storage<R>>(), from)
+    }
+}
+
+type Closure = HashMap<Entity, HashSet<Entity>>;
+
+/// Transitive closures of the relations registered with [`World::track_closure`].
+#[derive(Resource, Default)]
+pub struct Closures {
+    relations: HashMap<TypeId, Closure>,
+}
+
+impl Closures {
+    pub fn is_tracked<R: Relation>(&self) -> bool {
+        self.relations.contains_key(&TypeId::of::<Storage<R>>())
+    }
+
+    /// # Panics
+    ///
+    /// Panics if `R` is not tracked.
+    pub fn is_reachable<R: Relation>(&self, from: Entity, to: Entity) -> bool {
+        self.closure::<R>()
+            .get(&from)
+            .map_or(false, |targets| targets.contains(&to))
+    }
+
+    /// # Panics
+    ///
+    /// Panics if `R` is not tracked.
+    pub fn transitive_targets<R: Relation>(
+        &self,
+        from: Entity,
+    ) -> impl '_ + Iterator<Item = Entity> {
+        self.closure::<R>()
+            .get(&from)
+            .into_iter()
+            .flatten()
+            .copied()
+    }
+
+    fn closure<R: Relation>(&self) -> &Closure {
+        self.relations
+            .get(&TypeId::of::<Storage<R>>())
+            .expect("Relation closure should be tracked")
+    }
+}
+
+fn update_closure(
+    world: &mut World,
+    relation: Option<TypeId>,
+    mut func: impl FnMut(&World, &mut Closure, TypeId),
+) {
+    if !world.contains_resource::<Closures>() {
+        return;
+    }
+
+    world.resource_scope(|world, mut closures: Mut<Closures>| {
+        let closures = closures.bypass_change_detection();
+        let relations = match relation {
+            Some(relation) => vec![relation],
+            None => closures.relations.keys().copied().collect(),
+        };
+
+        for relation in relations {
+            if let Some(closure) = closures.relations.get_mut(&relation) {
+                func(world, closure, relation);
+            }
+        }
+    });
+}
+
+// Recomputes every foster that could reach `entity`.
+fn recompute_through(world: &World, closure: &mut Closure, relation: TypeId, entity: Entity) {
+    let fosters = closure
+        .iter()
+        .filter(|(foster, targets)| **foster == entity || targets.contains(&entity))
+        .map(|(foster, _)| *foster)
+        .collect::<Vec<_>>();
+
+    recompute(world, closure, relation, fosters);
+}
+
+fn recompute(world: &World, closure: &mut Closure, relation: TypeId, fosters: Vec<Entity>) {
+    for foster in fosters {
+        if world.get_entity(foster).is_none() {
+            closure.remove(&foster);
+            continue;
+        }
+
+        let targets = transitive_targets(|e| world.get::<Edges>(e), relation, foster);
+
+        if targets.is_empty() {
+            closure.remove(&foster);
+        } else {
+            closure.insert(foster, targets.into_iter().collect());
+        }
+    }
+}
+
+pub(crate) fn closure_insert(world: &mut World, relation: TypeId, foster: Entity, target: Entity) {
+    update_closure(world, Some(relation), |_, closure, _| {
+        let mut added = closure.get(&target).cloned().unwrap_or_default();
+        added.insert(target);
+
+        for (_, targets) in closure
+            .iter_mut()
+            .filter(|(entity, targets)| **entity == foster || targets.contains(&foster))
+        {
+            targets.extend(added.iter().copied());
+        }
+
+        closure.entry(foster).or_default().extend(added);
+    });
+}
+
+// Used by `SetBatch` once all of its edges are written. Every entry is scanned once and each
+// foster that could reach one of `fosters` is recomputed once, rather than once per edge.
+pub(crate) fn closure_insert_batch(world: &mut World, relation: TypeId, fosters: &HashSet<Entity>) {
+    update_closure(world, Some(relation), |world, closure, relation| {
+        let mut affected = fosters.clone();
+        affected.extend(
+            closure
+                .iter()
+                .filter(|(_, targets)| targets.iter().any(|target| fosters.contains(target)))
+                .map(|(foster, _)| *foster),
+        );
+
+        recompute(world, closure, relation, affected.into_iter().collect());
+    });
+}
+
+pub(crate) fn closure_remove(world: &mut World, relation: TypeId, foster: Entity) {
+    update_closure(world, Some(relation), |world, closure, relation| {
+        recompute_through(world, closure, relation, foster);
+    });
+}
+
+pub(crate) fn closure_despawn(world: &mut World, entity: Entity) {
+    update_closure(world, None, |world, closure, relation| {
+        recompute_through(world, closure, relation, entity);
+    });
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+    use crate::{component::TableStorage, system::SystemState};
+
+    struct In;
+
+    impl Relation for In {
+        type Storage = TableStorage;
+    }
+
+    struct Inside;
+
+    impl Relation for Inside {
+        type Storage = TableStorage;
+        const EXCLUSIVE: bool = true;
+        const DESPAWN_POLICY: DespawnPolicy = DespawnPolicy::Reparent;
+    }
+
+    fn sorted(mut entities: Vec<Entity>) -> Vec<Entity> {
+        entities.sort();
+        entities
+    }
+
+    #[test]
+    fn reachability() {
+        let mut world = World::new();
+        let [a, b, c, d] = [(); 4].map(|_| world.spawn_empty().id());
+
+        world.set_relations_batch([(a, b, In), (b, c, In), (c, a, In)]);
+
+        assert!(world.is_reachable::<In>(a, c));
+        assert!(world.is_reachable::<In>(a, a));
+        assert!(!world.is_reachable::<In>(a, d));
+        assert_eq!(
+            sorted(world.transitive_targets::<In>(b)),
+            sorted(vec![a, b, c])
+        );
+        assert!(world.transitive_targets::<In>(d).is_empty());
+
+        let mut system_state = SystemState::<Query<&Edges>>::new(&mut world);
+        let edges = system_state.get(&world);
+        assert!(edges.is_reachable::<In>(c, b));
+        assert_eq!(
+            sorted(edges.transitive_targets::<In>(c)),
+            sorted(vec![a, b, c])
+        );
+    }
+
+    #[test]
+    fn tracked_closure() {
+        let mut world = World::new();
+        let [house, room, chest, coin, other] = [(); 5].map(|_| world.spawn_empty().id());
+
+        world.set_relations_batch([(room, house, Inside), (chest, room, Inside)]);
+        world.track_closure::<Inside>();
+
+        let check = |world: &World| {
+            let closures = world.resource::<Closures>();
+            for foster in [house, room, chest, coin, other] {
+                assert_eq!(
+                    sorted(closures.transitive_targets::<Inside>(foster).collect()),
+                    sorted(world.transitive_targets::<Inside>(foster)),
+                );
+            }
+        };
+
+        check(&world);
+        assert!(world
+            .resource::<Closures>()
+            .is_reachable::<Inside>(chest, house));
+
+        Set {
+            foster: coin,
+            target: chest,
+            relation: Inside,
+        }
+        .write(&mut world);
+
+        check(&world);
+        assert!(world
+            .resource::<Closures>()
+            .is_reachable::<Inside>(coin, house));
+
+        // Exclusive overwrite moves the coin out of the house.
+        Set {
+            foster: coin,
+            target: other,
+            relation: Inside,
+        }
+        .write(&mut world);
+
+        check(&world);
+        assert!(!world
+            .resource::<Closures>()
+            .is_reachable::<Inside>(coin, house));
+
+        UnSet::<Inside> {
+            foster: coin,
+            target: other,
+            _phantom: PhantomData,
+        }
+        .write(&mut world);
+
+        check(&world);
+
+        Set {
+            foster: coin,
+            target: chest,
+            relation: Inside,
+        }
+        .write(&mut world);
+
+        // Reparenting moves the chest's contents into the room.
+        CheckedDespawn { entity: chest }.write(&mut world);
+
+        check(&world);
+        assert!(world
+            .resource::<Closures>()
+            .is_reachable::<Inside>(coin, house));
+        assert!(!world
+            .resource::<Closures>()
+            .is_reachable::<Inside>(coin, chest));
+
+        // Batches update the closure once for all of their edges.
+        world.set_relations_batch([(room, other, Inside), (other, house, Inside)]);
+
+        check(&world);
+        assert!(world
+            .resource::<Closures>()
+            .is_reachable::<Inside>(coin, other));
+    }
+}
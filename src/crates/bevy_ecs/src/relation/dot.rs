@@ -0,0 +1,279 @@
+use super::*;
+use crate::world::EntityRef;
+use std::{collections::VecDeque, fmt};
+
+// Graphviz export of relation graphs for debugging. Only relations registered with
+// `Dot::relation` are exported since edge labels need the concrete type to format values.
+// Names live outside of `bevy_ecs` so node labels are provided by a closure, for example
+// `.label(|entity| entity.get::<Name>().map(|name| name.to_string()))`.
+
+const COLORS: [&str; 6] = ["black", "blue", "red", "darkgreen", "purple", "orange"];
+const STYLES: [&str; 3] = ["solid", "dashed", "dotted"];
+
+type Label<'w> = Box<dyn 'w + Fn(EntityRef) -> Option<String>>;
+type Value<'w> = Box<dyn 'w + Fn(EntityRef, usize) -> Option<String>>;
+
+struct Kind<'w> {
+    relation: TypeId,
+    value: Value<'w>,
+}
+
+/// Writes the relation graph of a [`World`] in the DOT language.
+pub struct Dot<'w> {
+    world: &'w World,
+    kinds: Vec<Kind<'w>>,
+    label: Option<Label<'w>>,
+    around: Option<(Entity, usize)>,
+}
+
+impl<'w> Dot<'w> {
+    pub fn new(world: &'w World) -> Self {
+        Self {
+            world,
+            kinds: Vec::new(),
+            label: None,
+            around: None,
+        }
+    }
+
+    /// Exports edges of `R` labelled with the `Debug` output of their values.
+    /// Every relation gets its own edge color and style.
+    pub fn relation<R: Relation + fmt::Debug>(mut self) -> Self {
+        self.kinds.push(Kind {
+            relation: TypeId::of::<Storage<R>>(),
+            value: Box::new(|foster, index| {
+                let value = foster.get::<Storage<R>>()?.values.get(index)?;
+                Some(format!("{value:?}"))
+            }),
+        });
+        self
+    }
+
+    /// Labels nodes with the result of `label`. Entities are labelled with their id otherwise.
+    pub fn label(mut self, label: impl 'w + Fn(EntityRef) -> Option<String>) -> Self {
+        self.label = Some(Box::new(label));
+        self
+    }
+
+    /// Only exports entities within `depth` edges of `root` in either direction.
+    pub fn around(mut self, root: Entity, depth: usize) -> Self {
+        self.around = Some((root, depth));
+        self
+    }
+
+    fn neighbors(&self, edges: &Edges) -> Vec<Entity> {
+        let mut neighbors = Vec::new();
+
+        for kind in self.kinds.iter() {
+            for targets in edges
+                .targets
+                .iter()
+                .filter_map(|targets| targets.get(&kind.relation))
+            {
+                neighbors.extend(targets.keys().copied());
+            }
+
+            if let Some(fosters) = edges.fosters.get(&kind.relation) {
+                neighbors.extend(fosters.iter().copied());
+            }
+        }
+
+        neighbors
+    }
+
+    fn nodes(&self) -> Vec<Entity> {
+        let mut nodes = match self.around {
+            // A despawned root exports an empty graph.
+            Some((root, _)) if self.world.get_entity(root).is_none() => Vec::new(),
+            Some((root, depth)) => {
+                let mut nodes = HashSet::from_iter([root]);
+                let mut queue = VecDeque::from([(root, 0)]);
+
+                while let Some((entity, distance)) = queue.pop_front() {
+                    let Some(edges) = self.world.get::<Edges>(entity) else {
+                        continue;
+                    };
+
+                    if distance == depth {
+                        continue;
+                    }
+
+                    for neighbor in self.neighbors(edges) {
+                        if self.world.get_entity(neighbor).is_some() && nodes.insert(neighbor) {
+                            queue.push_back((neighbor, distance + 1));
+                        }
+                    }
+                }
+
+                nodes.into_iter().collect::<Vec<_>>()
+            }
+            None => self
+                .world
+                .iter_entities()
+                .filter(|entity| {
+                    entity
+                        .get::<Edges>()
+                        .map_or(false, |edges| !self.neighbors(edges).is_empty())
+                })
+                .map(|entity| entity.id())
+                .collect(),
+        };
+
+        nodes.sort();
+        nodes
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+impl fmt::Display for Dot<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let nodes = self.nodes();
+        let contains = |entity: &Entity| nodes.binary_search(entity).is_ok();
+
+        writeln!(f, "digraph {{")?;
+
+        for node in nodes.iter() {
+            let label = self
+                .label
+                .as_ref()
+                .and_then(|label| label(self.world.get_entity(*node)?))
+                .unwrap_or_else(|| format!("{node:?}"));
+
+            writeln!(f, "    \"{node:?}\" [label=\"{}\"];", escape(&label))?;
+        }
+
+        for node in nodes.iter() {
+            let Some(foster) = self.world.get_entity(*node) else {
+                continue;
+            };
+            let Some(edges) = foster.get::<Edges>() else {
+                continue;
+            };
+
+            for (kind_index, kind) in self.kinds.iter().enumerate() {
+                let mut targets = edges
+                    .targets
+                    .iter()
+                    .filter_map(|targets| targets.get(&kind.relation))
+                    .flatten()
+                    .filter(|(target, _)| contains(target))
+                    .collect::<Vec<_>>();
+
+                targets.sort();
+
+                for (target, index) in targets {
+                    let value = (kind.value)(foster, *index).unwrap_or_default();
+
+                    writeln!(
+                        f,
+                        "    \"{node:?}\" -> \"{target:?}\" [label=\"{}\", color={}, style={}];",
+                        escape(&value),
+                        COLORS[kind_index % COLORS.len()],
+                        STYLES[kind_index / COLORS.len() % STYLES.len()],
+                    )?;
+                }
+            }
+        }
+
+        writeln!(f, "}}")
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+    use crate::{self as bevy_ecs, component::TableStorage};
+
+    #[derive(Component)]
+    struct Name(&'static str);
+
+    #[derive(Debug)]
+    struct Owns(u32);
+
+    impl Relation for Owns {
+        type Storage = TableStorage;
+    }
+
+    #[derive(Debug)]
+    struct Likes;
+
+    impl Relation for Likes {
+        type Storage = TableStorage;
+    }
+
+    #[test]
+    fn dot_export() {
+        let mut world = World::new();
+        let ann = world.spawn(Name("ann")).id();
+        let ben = world.spawn(Name("\"ben\"")).id();
+        let cup = world.spawn(Name("cup")).id();
+        let far = world.spawn(Name("far")).id();
+
+        world.set_relations_batch([(ann, cup, Owns(2)), (far, cup, Owns(1))]);
+        world.set_relations_batch([(ann, ben, Likes), (ben, far, Likes)]);
+
+        let dot = Dot::new(&world)
+            .relation::<Owns>()
+            .relation::<Likes>()
+            .label(|entity| entity.get::<Name>().map(|name| name.0.to_string()))
+            .to_string();
+
+        assert_eq!(
+            dot,
+            format!(
+                r#"digraph {{
+    "{ann:?}" [label="ann"];
+    "{ben:?}" [label="\"ben\""];
+    "{cup:?}" [label="cup"];
+    "{far:?}" [label="far"];
+    "{ann:?}" -> "{cup:?}" [label="Owns(2)", color=black, style=solid];
+    "{ann:?}" -> "{ben:?}" [label="Likes", color=blue, style=solid];
+    "{ben:?}" -> "{far:?}" [label="Likes", color=blue, style=solid];
+    "{far:?}" -> "{cup:?}" [label="Owns(1)", color=black, style=solid];
+}}
+"#
+            )
+        );
+
+        // One hop around `cup` only through `Owns`.
+        let dot = Dot::new(&world)
+            .relation::<Owns>()
+            .around(cup, 1)
+            .to_string();
+
+        assert_eq!(
+            dot,
+            format!(
+                r#"digraph {{
+    "{ann:?}" [label="{ann:?}"];
+    "{cup:?}" [label="{cup:?}"];
+    "{far:?}" [label="{far:?}"];
+    "{ann:?}" -> "{cup:?}" [label="Owns(2)", color=black, style=solid];
+    "{far:?}" -> "{cup:?}" [label="Owns(1)", color=black, style=solid];
+}}
+"#
+            )
+        );
+
+        let dot = Dot::new(&world)
+            .relation::<Likes>()
+            .around(ann, 1)
+            .to_string();
+
+        assert!(dot.contains(&format!("\"{ben:?}\"")));
+        assert!(!dot.contains(&format!("\"{far:?}\"")));
+
+        world.despawn(ann);
+
+        let dot = Dot::new(&world)
+            .relation::<Likes>()
+            .label(|entity| entity.get::<Name>().map(|name| name.0.to_string()))
+            .around(ann, 1)
+            .to_string();
+
+        assert_eq!(dot, "digraph {\n}\n");
+    }
+}
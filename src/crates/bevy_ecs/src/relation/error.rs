@@ -0,0 +1,177 @@
+use super::*;
+use thiserror::Error;
+
+// `Set` and `UnSet` are forgiving: self relations are allowed, exclusive relations overwrite their
+// old target and invalid edges are skipped with a warning. `try_set` and `try_unset` reject all of
+// these up front and leave the world untouched.
+
+/// The error type returned by [`World::try_set`] and [`World::try_unset`].
+#[derive(Error, Debug, PartialEq, Eq, Clone, Copy)]
+pub enum RelationError {
+    #[error("The foster {0:?} does not exist.")]
+    MissingFoster(Entity),
+    #[error("The target {0:?} does not exist.")]
+    MissingTarget(Entity),
+    #[error("The entity {0:?} can not be related to itself.")]
+    SelfRelation(Entity),
+    #[error("The foster {foster:?} already has the exclusive target {target:?}.")]
+    ExclusiveConflict { foster: Entity, target: Entity },
+    #[error("The foster {foster:?} has no edge to {target:?}.")]
+    MissingEdge { foster: Entity, target: Entity },
+}
+
+pub(crate) fn check_entities(
+    world: &World,
+    foster: Entity,
+    target: Entity,
+) -> Result<(), RelationError> {
+    if world.get_entity(foster).is_none() {
+        Err(RelationError::MissingFoster(foster))
+    } else if world.get_entity(target).is_none() {
+        Err(RelationError::MissingTarget(target))
+    } else {
+        Ok(())
+    }
+}
+
+pub(crate) fn has_edge<R: Relation>(world: &World, foster: Entity, target: Entity) -> bool {
+    world.get::<Edges>(foster).map_or(false, |edges| {
+        edges.targets_of::<R>().any(|(t, _)| *t == target)
+    })
+}
+
+impl World {
+    /// Sets an edge of relation `R` from `foster` to `target`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error without changing anything if either entity doesn't exist,
+    /// `foster` and `target` are the same entity or `R` is exclusive and `foster` already
+    /// has a different target.
+    pub fn try_set<R: Relation>(
+        &mut self,
+        foster: Entity,
+        target: Entity,
+        relation: R,
+    ) -> Result<(), RelationError> {
+        check_entities(self, foster, target)?;
+
+        if foster == target {
+            return Err(RelationError::SelfRelation(foster));
+        }
+
+        if R::EXCLUSIVE {
+            if let Some((old_target, _)) = self
+                .get::<Edges>(foster)
+                .and_then(|edges| edges.targets_of::<R>().find(|(t, _)| **t != target))
+            {
+                return Err(RelationError::ExclusiveConflict {
+                    foster,
+                    target: *old_target,
+                });
+            }
+        }
+
+        Set {
+            foster,
+            target,
+            relation,
+        }
+        .write(self);
+
+        Ok(())
+    }
+
+    /// Removes the edge of relation `R` from `foster` to `target`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either entity doesn't exist or there is no such edge.
+    pub fn try_unset<R: Relation>(
+        &mut self,
+        foster: Entity,
+        target: Entity,
+    ) -> Result<(), RelationError> {
+        check_entities(self, foster, target)?;
+
+        if !has_edge::<R>(self, foster, target) {
+            return Err(RelationError::MissingEdge { foster, target });
+        }
+
+        UnSet::<R> {
+            foster,
+            target,
+            _phantom: PhantomData,
+        }
+        .write(self);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+    use crate::component::TableStorage;
+
+    struct Likes;
+
+    impl Relation for Likes {
+        type Storage = TableStorage;
+    }
+
+    struct ChildOf;
+
+    impl Relation for ChildOf {
+        type Storage = TableStorage;
+        const EXCLUSIVE: bool = true;
+    }
+
+    #[test]
+    fn fallible_commands() {
+        let mut world = World::new();
+        let [a, b, c] = [(); 3].map(|_| world.spawn_empty().id());
+        let gone = world.spawn_empty().id();
+        world.despawn(gone);
+
+        assert_eq!(world.try_set(a, b, Likes), Ok(()));
+        assert_eq!(
+            world.try_set(gone, b, Likes),
+            Err(RelationError::MissingFoster(gone))
+        );
+        assert_eq!(
+            world.try_set(a, gone, Likes),
+            Err(RelationError::MissingTarget(gone))
+        );
+        assert_eq!(
+            world.try_set(a, a, Likes),
+            Err(RelationError::SelfRelation(a))
+        );
+
+        assert_eq!(world.try_set(a, b, ChildOf), Ok(()));
+        assert_eq!(world.try_set(a, b, ChildOf), Ok(()));
+        assert_eq!(
+            world.try_set(a, c, ChildOf),
+            Err(RelationError::ExclusiveConflict {
+                foster: a,
+                target: b
+            })
+        );
+        assert!(has_edge::<ChildOf>(&world, a, b));
+        assert!(!has_edge::<ChildOf>(&world, a, c));
+
+        assert_eq!(world.try_unset::<Likes>(a, b), Ok(()));
+        assert_eq!(
+            world.try_unset::<Likes>(a, b),
+            Err(RelationError::MissingEdge {
+                foster: a,
+                target: b
+            })
+        );
+        assert_eq!(
+            world.try_unset::<Likes>(a, gone),
+            Err(RelationError::MissingTarget(gone))
+        );
+        assert!(has_edge::<ChildOf>(&world, a, b));
+    }
+}
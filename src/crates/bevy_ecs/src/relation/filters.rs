@@ -0,0 +1,386 @@
+use super::*;
+use crate::{
+    archetype::{Archetype, ArchetypeComponentId},
+    component::{ComponentId, Tick},
+    query::{
+        Access, ArchetypeFilter, FilteredAccess, ROQueryItem, ReadOnlyWorldQuery, With, WorldQuery,
+    },
+    storage::{Table, TableRow},
+};
+
+// Query filters over relations. Fosters are first narrowed down at archetype level by requiring
+// `Storage<R>` and then checked per entity so fosters whose edges were all removed are excluded.
+// Targets carry no relation specific component so `TargetedBy` can only check per entity.
+// Filters are types so they can't name a specific entity. Fosters of a single target are selected
+// with `Query::iter_targeting` instead, which checks each foster like `RelatedTo` does.
+
+/// Selects fosters with any target of relation `R`.
+pub type HasRelation<R> = RelatedTo<R, ()>;
+
+/// Selects fosters with any target of relation `R` that matches the archetypal filter `F`.
+///
+/// To select the fosters of one specific target use [`Query::iter_targeting`].
+pub struct RelatedTo<R, F = ()>(PhantomData<(R, F)>);
+
+/// Selects targets of relation `R` with at least one foster.
+pub struct TargetedBy<R>(PhantomData<R>);
+
+type FosterQuery<R> = (&'static Edges, With<Storage<R>>);
+
+#[doc(hidden)]
+pub struct RelatedToFetch<'w, R: Relation, F: WorldQuery> {
+    inner: <FosterQuery<R> as WorldQuery>::Fetch<'w>,
+    filter: F::State,
+    world: &'w World,
+}
+
+#[doc(hidden)]
+pub struct RelatedToState<R: Relation, F: WorldQuery> {
+    inner: <FosterQuery<R> as WorldQuery>::State,
+    filter: F::State,
+}
+
+// SAFETY: Reads `Edges` and defers to `FosterQuery<R>` for access.
+// `F` is archetypal so matching targets only reads archetype metadata which needs no access.
+unsafe impl<R, F> WorldQuery for RelatedTo<R, F>
+where
+    R: Relation,
+    F: ReadOnlyWorldQuery + ArchetypeFilter,
+    F::State: Clone,
+{
+    type Fetch<'w> = RelatedToFetch<'w, R, F>;
+    type Item<'w> = bool;
+    type ReadOnly = Self;
+    type State = RelatedToState<R, F>;
+
+    fn shrink<'wlong: 'wshort, 'wshort>(item: Self::Item<'wlong>) -> Self::Item<'wshort> {
+        item
+    }
+
+    const IS_DENSE: bool = <FosterQuery<R> as WorldQuery>::IS_DENSE;
+
+    const IS_ARCHETYPAL: bool = false;
+
+    unsafe fn init_fetch<'w>(
+        world: &'w World,
+        state: &Self::State,
+        last_run: Tick,
+        this_run: Tick,
+    ) -> Self::Fetch<'w> {
+        RelatedToFetch {
+            inner: FosterQuery::<R>::init_fetch(world, &state.inner, last_run, this_run),
+            filter: state.filter.clone(),
+            world,
+        }
+    }
+
+    unsafe fn clone_fetch<'w>(fetch: &Self::Fetch<'w>) -> Self::Fetch<'w> {
+        RelatedToFetch {
+            inner: FosterQuery::<R>::clone_fetch(&fetch.inner),
+            filter: fetch.filter.clone(),
+            world: fetch.world,
+        }
+    }
+
+    #[inline]
+    unsafe fn set_archetype<'w>(
+        fetch: &mut Self::Fetch<'w>,
+        state: &Self::State,
+        archetype: &'w Archetype,
+        table: &'w Table,
+    ) {
+        FosterQuery::<R>::set_archetype(&mut fetch.inner, &state.inner, archetype, table);
+    }
+
+    #[inline]
+    unsafe fn set_table<'w>(fetch: &mut Self::Fetch<'w>, state: &Self::State, table: &'w Table) {
+        FosterQuery::<R>::set_table(&mut fetch.inner, &state.inner, table);
+    }
+
+    #[inline(always)]
+    unsafe fn fetch<'w>(
+        fetch: &mut Self::Fetch<'w>,
+        entity: Entity,
+        table_row: TableRow,
+    ) -> Self::Item<'w> {
+        let (edges, _) = FosterQuery::<R>::fetch(&mut fetch.inner, entity, table_row);
+
+        edges.targets_of::<R>().any(|(target, _)| {
+            fetch
+                .world
+                .entities()
+                .get(*target)
+                .and_then(|location| fetch.world.archetypes().get(location.archetype_id))
+                .map_or(false, |archetype| {
+                    F::matches_component_set(&fetch.filter, &|id| archetype.contains(id))
+                })
+        })
+    }
+
+    #[inline(always)]
+    unsafe fn filter_fetch(
+        fetch: &mut Self::Fetch<'_>,
+        entity: Entity,
+        table_row: TableRow,
+    ) -> bool {
+        Self::fetch(fetch, entity, table_row)
+    }
+
+    fn update_component_access(state: &Self::State, access: &mut FilteredAccess<ComponentId>) {
+        FosterQuery::<R>::update_component_access(&state.inner, access);
+    }
+
+    fn update_archetype_component_access(
+        state: &Self::State,
+        archetype: &Archetype,
+        access: &mut Access<ArchetypeComponentId>,
+    ) {
+        FosterQuery::<R>::update_archetype_component_access(&state.inner, archetype, access);
+    }
+
+    fn init_state(world: &mut World) -> Self::State {
+        RelatedToState {
+            inner: FosterQuery::<R>::init_state(world),
+            filter: F::init_state(world),
+        }
+    }
+
+    fn matches_component_set(
+        state: &Self::State,
+        set_contains_id: &impl Fn(ComponentId) -> bool,
+    ) -> bool {
+        FosterQuery::<R>::matches_component_set(&state.inner, set_contains_id)
+    }
+}
+
+// SAFETY: Only reads `Edges` and archetype metadata.
+unsafe impl<R, F> ReadOnlyWorldQuery for RelatedTo<R, F>
+where
+    R: Relation,
+    F: ReadOnlyWorldQuery + ArchetypeFilter,
+    F::State: Clone,
+{
+}
+
+impl<'w, 's, Q, F, R> Query<'w, 's, (Q, Relations<R>), F>
+where
+    Q: WorldQuery,
+    F: ReadOnlyWorldQuery,
+    R: RelationQuerySet,
+{
+    /// Iterates the items of fosters with an edge of relation `T` to `target`.
+    pub fn iter_targeting<T: Relation>(
+        &self,
+        target: Entity,
+    ) -> impl '_ + Iterator<Item = ROQueryItem<'_, Q>> {
+        self.iter().filter_map(move |(item, relations)| {
+            relations.edges.target_index::<T>(target).map(|_| item)
+        })
+    }
+}
+
+#[doc(hidden)]
+pub struct TargetedByFetch<'w> {
+    inner: <&'static Edges as WorldQuery>::Fetch<'w>,
+}
+
+// SAFETY: Reads `Edges` and defers to `&Edges` for access.
+unsafe impl<R: Relation> WorldQuery for TargetedBy<R> {
+    type Fetch<'w> = TargetedByFetch<'w>;
+    type Item<'w> = bool;
+    type ReadOnly = Self;
+    type State = ComponentId;
+
+    fn shrink<'wlong: 'wshort, 'wshort>(item: Self::Item<'wlong>) -> Self::Item<'wshort> {
+        item
+    }
+
+    const IS_DENSE: bool = <&Edges as WorldQuery>::IS_DENSE;
+
+    const IS_ARCHETYPAL: bool = false;
+
+    unsafe fn init_fetch<'w>(
+        world: &'w World,
+        state: &Self::State,
+        last_run: Tick,
+        this_run: Tick,
+    ) -> Self::Fetch<'w> {
+        TargetedByFetch {
+            inner: <&Edges>::init_fetch(world, state, last_run, this_run),
+        }
+    }
+
+    unsafe fn clone_fetch<'w>(fetch: &Self::Fetch<'w>) -> Self::Fetch<'w> {
+        TargetedByFetch {
+            inner: <&Edges>::clone_fetch(&fetch.inner),
+        }
+    }
+
+    #[inline]
+    unsafe fn set_archetype<'w>(
+        fetch: &mut Self::Fetch<'w>,
+        state: &Self::State,
+        archetype: &'w Archetype,
+        table: &'w Table,
+    ) {
+        <&Edges>::set_archetype(&mut fetch.inner, state, archetype, table);
+    }
+
+    #[inline]
+    unsafe fn set_table<'w>(fetch: &mut Self::Fetch<'w>, state: &Self::State, table: &'w Table) {
+        <&Edges>::set_table(&mut fetch.inner, state, table);
+    }
+
+    #[inline(always)]
+    unsafe fn fetch<'w>(
+        fetch: &mut Self::Fetch<'w>,
+        entity: Entity,
+        table_row: TableRow,
+    ) -> Self::Item<'w> {
+        <&Edges>::fetch(&mut fetch.inner, entity, table_row)
+            .fosters
+            .get(&TypeId::of::<Storage<R>>())
+            .map_or(false, |fosters| !fosters.is_empty())
+    }
+
+    #[inline(always)]
+    unsafe fn filter_fetch(
+        fetch: &mut Self::Fetch<'_>,
+        entity: Entity,
+        table_row: TableRow,
+    ) -> bool {
+        Self::fetch(fetch, entity, table_row)
+    }
+
+    fn update_component_access(state: &Self::State, access: &mut FilteredAccess<ComponentId>) {
+        <&Edges>::update_component_access(state, access);
+    }
+
+    fn update_archetype_component_access(
+        state: &Self::State,
+        archetype: &Archetype,
+        access: &mut Access<ArchetypeComponentId>,
+    ) {
+        <&Edges>::update_archetype_component_access(state, archetype, access);
+    }
+
+    fn init_state(world: &mut World) -> Self::State {
+        <&Edges>::init_state(world)
+    }
+
+    fn matches_component_set(
+        state: &Self::State,
+        set_contains_id: &impl Fn(ComponentId) -> bool,
+    ) -> bool {
+        <&Edges>::matches_component_set(state, set_contains_id)
+    }
+}
+
+// SAFETY: Only reads `Edges`.
+unsafe impl<R: Relation> ReadOnlyWorldQuery for TargetedBy<R> {}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+    use crate::{self as bevy_ecs, component::TableStorage, prelude::*, system::SystemState};
+
+    #[derive(Component)]
+    struct Name(&'static str);
+
+    #[derive(Component)]
+    struct Hostile;
+
+    struct Attacks;
+
+    impl Relation for Attacks {
+        type Storage = TableStorage;
+    }
+
+    struct Follows;
+
+    impl Relation for Follows {
+        type Storage = TableStorage;
+    }
+
+    fn names<F: 'static + ReadOnlyWorldQuery>(world: &mut World) -> Vec<&'static str> {
+        let mut system_state = SystemState::<Query<&Name, F>>::new(world);
+        let mut names = system_state
+            .get(world)
+            .iter()
+            .map(|name| name.0)
+            .collect::<Vec<_>>();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn relation_filters() {
+        let mut world = World::new();
+        let ann = world.spawn(Name("ann")).id();
+        let ben = world.spawn(Name("ben")).id();
+        let cat = world.spawn((Name("cat"), Hostile)).id();
+        let dan = world.spawn(Name("dan")).id();
+
+        Set {
+            foster: ann,
+            target: cat,
+            relation: Attacks,
+        }
+        .write(&mut world);
+
+        Set {
+            foster: ben,
+            target: dan,
+            relation: Attacks,
+        }
+        .write(&mut world);
+
+        Set {
+            foster: dan,
+            target: ann,
+            relation: Follows,
+        }
+        .write(&mut world);
+
+        assert_eq!(
+            names::<HasRelation<Attacks>>(&mut world),
+            vec!["ann", "ben"]
+        );
+        assert_eq!(names::<HasRelation<Follows>>(&mut world), vec!["dan"]);
+        assert_eq!(names::<TargetedBy<Attacks>>(&mut world), vec!["cat", "dan"]);
+        assert_eq!(
+            names::<RelatedTo<Attacks, With<Hostile>>>(&mut world),
+            vec!["ann"]
+        );
+        assert_eq!(
+            names::<(HasRelation<Attacks>, Without<Hostile>, TargetedBy<Follows>)>(&mut world),
+            vec!["ann"]
+        );
+        assert_eq!(
+            names::<Or<(TargetedBy<Follows>, RelatedTo<Attacks, With<Hostile>>)>>(&mut world),
+            vec!["ann"]
+        );
+
+        let mut system_state = SystemState::<Query<(&Name, Relations<&Attacks>)>>::new(&mut world);
+        let query = system_state.get_mut(&mut world);
+        assert_eq!(
+            query
+                .iter_targeting::<Attacks>(dan)
+                .map(|name| name.0)
+                .collect::<Vec<_>>(),
+            vec!["ben"]
+        );
+        assert_eq!(query.iter_targeting::<Follows>(dan).count(), 0);
+
+        UnSet::<Attacks> {
+            foster: ben,
+            target: dan,
+            _phantom: PhantomData,
+        }
+        .write(&mut world);
+
+        // `ben` still has an empty `Storage<Attacks>`.
+        assert_eq!(names::<HasRelation<Attacks>>(&mut world), vec!["ann"]);
+        assert_eq!(names::<TargetedBy<Attacks>>(&mut world), vec!["cat"]);
+    }
+}
@@ -0,0 +1,142 @@
+use super::*;
+use crate::{self as bevy_ecs, system::SystemParam};
+
+// Relation values live in the `Storage<R>` of their foster so a target can only reach them by
+// looking up each foster. A `WorldQuery` item can't do this because its access is limited to the
+// archetypes it matches, which is why these are system params like `RelationGraph`.
+// Each foster has at most one edge of `R` to a given target, so the values pointing at a target
+// are in distinct entities and can be borrowed mutably one after another.
+
+/// Values of relation `R` seen from the target, as `(foster, &R)` pairs.
+///
+/// This is a [`SystemParam`] rather than a query item since the values are stored on the
+/// fosters, which a query for the target can't access. Use it next to the target's query and
+/// pass the target entity to [`Fosters::iter`].
+#[derive(SystemParam)]
+pub struct Fosters<'w, 's, R: Relation> {
+    query: Query<'w, 's, (&'static Edges, Option<StorageWorldQuery<R>>)>,
+}
+
+impl<'w, 's, R: Relation> Fosters<'w, 's, R> {
+    /// Every foster with an edge of `R` to `target` and the value of that edge.
+    pub fn iter(&self, target: Entity) -> impl '_ + Iterator<Item = (Entity, &R)> {
+        fosters_of(
+            |entity| {
+                let (edges, storage) = self.query.get(entity).ok()?;
+                Some((edges, storage.map(|storage| storage.storage)))
+            },
+            target,
+        )
+    }
+}
+
+/// Mutable values of relation `R` seen from the target, as `(foster, &mut R)` pairs.
+///
+/// Like [`Fosters`] this is a [`SystemParam`] rather than a query item.
+#[derive(SystemParam)]
+pub struct FostersMut<'w, 's, R: Relation> {
+    query: Query<'w, 's, (&'static Edges, Option<StorageWorldQueryMut<R>>)>,
+}
+
+impl<'w, 's, R: Relation> FostersMut<'w, 's, R> {
+    /// Every foster with an edge of `R` to `target` and the value of that edge.
+    pub fn iter(&self, target: Entity) -> impl '_ + Iterator<Item = (Entity, &R)> {
+        fosters_of(
+            |entity| {
+                let (edges, storage) = self.query.get(entity).ok()?;
+                Some((edges, storage.map(|storage| storage.storage)))
+            },
+            target,
+        )
+    }
+
+    /// Calls `func` with every foster with an edge of `R` to `target` and the value of that
+    /// edge.
+    pub fn for_each_mut(&mut self, target: Entity, mut func: impl FnMut(Entity, &mut R)) {
+        let fosters = self
+            .query
+            .get(target)
+            .ok()
+            .and_then(|(edges, _)| edges.fosters.get(&TypeId::of::<Storage<R>>()))
+            .into_iter()
+            .flatten()
+            .copied()
+            .collect::<Vec<_>>();
+
+        for foster in fosters {
+            let Ok((edges, Some(mut storage))) = self.query.get_mut(foster) else {
+                continue;
+            };
+
+            let Some(index) = edges.target_index::<R>(target) else {
+                continue;
+            };
+
+            if let Some(value) = storage.storage.values.get_mut(index) {
+                func(foster, value);
+            }
+        }
+    }
+}
+
+// Shared by `Fosters` and `FostersMut`, whose queries only differ in the mutability of the storage.
+fn fosters_of<'a, R: Relation>(
+    get: impl 'a + Fn(Entity) -> Option<(&'a Edges, Option<&'a Storage<R>>)>,
+    target: Entity,
+) -> impl 'a + Iterator<Item = (Entity, &'a R)> {
+    get(target)
+        .and_then(|(edges, _)| edges.fosters.get(&TypeId::of::<Storage<R>>()))
+        .into_iter()
+        .flatten()
+        .filter_map(move |foster| {
+            let (edges, storage) = get(*foster)?;
+            let index = edges.target_index::<R>(target)?;
+            Some((*foster, storage?.values.get(index)?))
+        })
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+    use crate::{component::TableStorage, system::SystemState};
+
+    struct Attacking(u32);
+
+    impl Relation for Attacking {
+        type Storage = TableStorage;
+    }
+
+    #[test]
+    fn values_from_target() {
+        let mut world = World::new();
+        let [ann, ben, cat, dan] = [(); 4].map(|_| world.spawn_empty().id());
+
+        world.set_relations_batch([
+            (ann, cat, Attacking(3)),
+            (ann, dan, Attacking(100)),
+            (ben, cat, Attacking(4)),
+        ]);
+
+        let mut system_state = SystemState::<Fosters<Attacking>>::new(&mut world);
+        let fosters = system_state.get(&world);
+
+        let mut damage = fosters
+            .iter(cat)
+            .map(|(foster, attacking)| (foster, attacking.0))
+            .collect::<Vec<_>>();
+        damage.sort();
+        assert_eq!(damage, vec![(ann, 3), (ben, 4)]);
+        assert_eq!(fosters.iter(ann).count(), 0);
+
+        let mut system_state = SystemState::<FostersMut<Attacking>>::new(&mut world);
+        let mut fosters = system_state.get_mut(&mut world);
+        fosters.for_each_mut(cat, |_, attacking| attacking.0 *= 2);
+
+        let total = fosters
+            .iter(cat)
+            .map(|(_, attacking)| attacking.0)
+            .sum::<u32>();
+        assert_eq!(total, 14);
+        assert_eq!(fosters.iter(dan).next().unwrap().1 .0, 100);
+    }
+}
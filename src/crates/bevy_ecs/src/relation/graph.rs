@@ -0,0 +1,119 @@
+use super::*;
+use crate::{self as bevy_ecs, system::SystemParam};
+
+// Read-only view of a single relation for algorithms that need random access to the graph.
+// Only `Edges` and `Storage<R>` are read so systems using it run in parallel with anything that
+// doesn't write relations.
+
+/// Adjacency of relation `R` where edges point from foster to target.
+#[derive(SystemParam)]
+pub struct RelationGraph<'w, 's, R: Relation> {
+    query: Query<'w, 's, (Entity, &'static Edges, Option<StorageWorldQuery<R>>)>,
+}
+
+impl<'w, 's, R: Relation> RelationGraph<'w, 's, R> {
+    /// Entities with at least one `R` edge in either direction.
+    pub fn nodes(&self) -> impl '_ + Iterator<Item = Entity> {
+        self.query
+            .iter()
+            .filter(|(_, edges, _)| {
+                edges.targets_of::<R>().next().is_some()
+                    || edges
+                        .fosters
+                        .get(&TypeId::of::<Storage<R>>())
+                        .map_or(false, |fosters| !fosters.is_empty())
+            })
+            .map(|(entity, ..)| entity)
+    }
+
+    /// Targets of `entity`.
+    pub fn neighbors(&self, entity: Entity) -> impl '_ + Iterator<Item = Entity> {
+        self.query
+            .get(entity)
+            .into_iter()
+            .flat_map(|(_, edges, _)| edges.targets_of::<R>().map(|(target, _)| *target))
+    }
+
+    /// Fosters of `entity`.
+    pub fn in_neighbors(&self, entity: Entity) -> impl '_ + Iterator<Item = Entity> {
+        self.query
+            .get(entity)
+            .into_iter()
+            .filter_map(|(_, edges, _)| edges.fosters.get(&TypeId::of::<Storage<R>>()))
+            .flatten()
+            .copied()
+    }
+
+    /// Number of targets and fosters of `entity`.
+    pub fn degree(&self, entity: Entity) -> usize {
+        self.neighbors(entity).count() + self.in_neighbors(entity).count()
+    }
+
+    /// Value of the edge from `foster` to `target`.
+    pub fn edge_value(&self, foster: Entity, target: Entity) -> Option<&R> {
+        let (_, edges, storage) = self.query.get(foster).ok()?;
+        let index = edges.target_index::<R>(target)?;
+
+        storage?.storage.values.get(index)
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+    use crate::{component::TableStorage, prelude::*, system::SystemState};
+
+    #[derive(Component)]
+    struct Visited(bool);
+
+    struct Road(u32);
+
+    impl Relation for Road {
+        type Storage = TableStorage;
+    }
+
+    struct Rail;
+
+    impl Relation for Rail {
+        type Storage = TableStorage;
+    }
+
+    fn sorted(entities: impl Iterator<Item = Entity>) -> Vec<Entity> {
+        let mut entities = entities.collect::<Vec<_>>();
+        entities.sort();
+        entities
+    }
+
+    #[test]
+    fn adjacency() {
+        let mut world = World::new();
+        let a = world.spawn(Visited(false)).id();
+        let b = world.spawn(Visited(false)).id();
+        let c = world.spawn(Visited(false)).id();
+        let d = world.spawn(Visited(false)).id();
+
+        world.set_relations_batch([(a, b, Road(3)), (a, c, Road(5)), (c, b, Road(1))]);
+        world.set_relations_batch([(c, d, Rail)]);
+
+        // Disjoint from every other component so it can share a system with `&mut Visited`.
+        let mut system_state =
+            SystemState::<(RelationGraph<Road>, Query<&mut Visited>)>::new(&mut world);
+        let (graph, mut visited) = system_state.get_mut(&mut world);
+
+        assert_eq!(sorted(graph.nodes()), vec![a, b, c]);
+        assert_eq!(sorted(graph.neighbors(a)), vec![b, c]);
+        assert_eq!(sorted(graph.in_neighbors(b)), vec![a, c]);
+        assert_eq!(graph.neighbors(d).count(), 0);
+        assert_eq!(graph.degree(c), 2);
+        assert_eq!(graph.degree(d), 0);
+        assert_eq!(graph.edge_value(a, c).map(|road| road.0), Some(5));
+        assert_eq!(graph.edge_value(c, a).map(|road| road.0), None);
+        assert!(graph.edge_value(d, a).is_none());
+
+        for entity in graph.neighbors(a) {
+            visited.get_mut(entity).unwrap().0 = true;
+        }
+
+        assert!(visited.get(b).unwrap().0 && !visited.get(d).unwrap().0);
+    }
+}
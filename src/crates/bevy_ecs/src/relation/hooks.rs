@@ -0,0 +1,48 @@
+use super::*;
+use crate::system::Resource;
+
+// `Relation::on_set` and `Relation::on_remove` keep components derived from a relation in sync,
+// for example `Parent` and `Children` in `bevy_hierarchy`. Typed commands call them directly.
+// Despawn policies only know the `TypeId` of a relation so the hooks of every relation that has
+// been set at least once are kept here.
+#[derive(Resource, Default)]
+pub(crate) struct RelationHooks {
+    on_set: HashMap<TypeId, fn(&mut World, Entity, Entity)>,
+    on_remove: HashMap<TypeId, fn(&mut World, Entity, Entity)>,
+}
+
+impl RelationHooks {
+    // Relations are registered before their first edge is set, so this also tells whether
+    // `type_id` is the `Storage` of a relation.
+    pub(crate) fn contains(&self, type_id: TypeId) -> bool {
+        self.on_set.contains_key(&type_id)
+    }
+}
+
+pub(crate) fn register_hooks<R: Relation>(world: &mut World) {
+    let relation = TypeId::of::<Storage<R>>();
+    let mut hooks = world.get_resource_or_insert_with(RelationHooks::default);
+
+    if !hooks.on_set.contains_key(&relation) {
+        hooks.on_set.insert(relation, R::on_set);
+        hooks.on_remove.insert(relation, R::on_remove);
+    }
+}
+
+pub(crate) fn hook_set(world: &mut World, relation: TypeId, foster: Entity, target: Entity) {
+    if let Some(on_set) = world
+        .get_resource::<RelationHooks>()
+        .and_then(|hooks| hooks.on_set.get(&relation).copied())
+    {
+        on_set(world, foster, target);
+    }
+}
+
+pub(crate) fn hook_remove(world: &mut World, relation: TypeId, foster: Entity, target: Entity) {
+    if let Some(on_remove) = world
+        .get_resource::<RelationHooks>()
+        .and_then(|hooks| hooks.on_remove.get(&relation).copied())
+    {
+        on_remove(world, foster, target);
+    }
+}
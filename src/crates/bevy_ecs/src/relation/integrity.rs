@@ -0,0 +1,274 @@
+use super::*;
+use std::fmt;
+
+// Consistency checks for the bookkeeping behind a relation. Every target recorded on a foster
+// must list the foster back, neither side may refer to a despawned entity and every storage
+// index must be in bounds. Commands uphold these invariants and `.expect` them, so a broken edge
+// panics deep inside `Set` or a despawn policy. Checking and repairing up front turns that into
+// a report.
+// TODO: `Pairs`, `Closures` and `Islands` are not checked.
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RelationIssue {
+    /// `foster` has an edge to a `target` that no longer exists.
+    DespawnedTarget { foster: Entity, target: Entity },
+    /// `target` lists a `foster` that no longer exists.
+    DespawnedFoster { foster: Entity, target: Entity },
+    /// `foster` has an edge to `target` but `target` doesn't list `foster`.
+    MissingFoster { foster: Entity, target: Entity },
+    /// `target` lists `foster` but `foster` has no edge to `target`.
+    MissingTarget { foster: Entity, target: Entity },
+    /// The edge from `foster` to `target` points past the end of the foster's storage.
+    StorageOutOfBounds {
+        foster: Entity,
+        target: Entity,
+        index: usize,
+    },
+}
+
+impl fmt::Display for RelationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DespawnedTarget { foster, target } => {
+                write!(f, "{foster:?} has an edge to despawned target {target:?}")
+            }
+            Self::DespawnedFoster { foster, target } => {
+                write!(f, "{target:?} is targeted by despawned foster {foster:?}")
+            }
+            Self::MissingFoster { foster, target } => {
+                write!(
+                    f,
+                    "{foster:?} has an edge to {target:?} which doesn't list it"
+                )
+            }
+            Self::MissingTarget { foster, target } => {
+                write!(f, "{target:?} lists {foster:?} which has no edge to it")
+            }
+            Self::StorageOutOfBounds {
+                foster,
+                target,
+                index,
+            } => write!(
+                f,
+                "{foster:?} has an edge to {target:?} with out of bounds storage index {index}"
+            ),
+        }
+    }
+}
+
+impl World {
+    /// Returns every broken invariant of relation `R`.
+    pub fn check_relation<R: Relation>(&self) -> Vec<RelationIssue> {
+        let relation = TypeId::of::<Storage<R>>();
+        let mut issues = Vec::new();
+
+        for entity in self.iter_entities() {
+            let Some(edges) = entity.get::<Edges>() else {
+                continue;
+            };
+
+            let len = entity
+                .get::<Storage<R>>()
+                .map_or(0, |storage| storage.values.len());
+
+            for (target, index) in edges
+                .targets
+                .iter()
+                .filter_map(|targets| targets.get(&relation))
+                .flatten()
+            {
+                let (foster, target) = (entity.id(), *target);
+
+                if self.get_entity(target).is_none() {
+                    issues.push(RelationIssue::DespawnedTarget { foster, target });
+                } else if !self.get::<Edges>(target).map_or(false, |edges| {
+                    edges
+                        .fosters
+                        .get(&relation)
+                        .map_or(false, |fosters| fosters.contains(&foster))
+                }) {
+                    issues.push(RelationIssue::MissingFoster { foster, target });
+                }
+
+                if *index >= len {
+                    issues.push(RelationIssue::StorageOutOfBounds {
+                        foster,
+                        target,
+                        index: *index,
+                    });
+                }
+            }
+
+            for foster in edges.fosters.get(&relation).into_iter().flatten() {
+                let (foster, target) = (*foster, entity.id());
+
+                if self.get_entity(foster).is_none() {
+                    issues.push(RelationIssue::DespawnedFoster { foster, target });
+                } else if !self.get::<Edges>(foster).map_or(false, |edges| {
+                    edges
+                        .targets
+                        .iter()
+                        .filter_map(|targets| targets.get(&relation))
+                        .any(|targets| targets.contains_key(&target))
+                }) {
+                    issues.push(RelationIssue::MissingTarget { foster, target });
+                }
+            }
+        }
+
+        issues
+    }
+
+    /// Repairs `issues` found by [`World::check_relation`]. Edges that can't be trusted are
+    /// dropped while edges that are only missing their foster entry get it back.
+    pub fn repair_relation<R: Relation>(&mut self, issues: &[RelationIssue]) {
+        let relation = TypeId::of::<Storage<R>>();
+
+        let remove_target = |world: &mut World, foster: Entity, target: Entity| {
+            if let Some(mut edges) = world.get_mut::<Edges>(foster) {
+                for targets in edges
+                    .targets
+                    .iter_mut()
+                    .filter_map(|targets| targets.get_mut(&relation))
+                {
+                    targets.shift_remove(&target);
+                }
+            }
+        };
+
+        let remove_foster = |world: &mut World, foster: Entity, target: Entity| {
+            if let Some(mut edges) = world.get_mut::<Edges>(target) {
+                if let Some(fosters) = edges.fosters.get_mut(&relation) {
+                    fosters.remove(&foster);
+                }
+            }
+        };
+
+        for issue in issues {
+            match *issue {
+                RelationIssue::DespawnedTarget { foster, target } => {
+                    remove_target(self, foster, target);
+                }
+                RelationIssue::DespawnedFoster { foster, target }
+                | RelationIssue::MissingTarget { foster, target } => {
+                    remove_foster(self, foster, target);
+                }
+                RelationIssue::MissingFoster { foster, target } => {
+                    let Some(mut target_mut) = self.get_entity_mut(target) else {
+                        continue;
+                    };
+
+                    if !target_mut.contains::<Edges>() {
+                        target_mut.insert(Edges::default());
+                    }
+
+                    target_mut
+                        .get_mut::<Edges>()
+                        .expect("Edge component should exist")
+                        .fosters
+                        .entry(relation)
+                        .or_default()
+                        .insert(foster);
+                }
+                RelationIssue::StorageOutOfBounds { foster, target, .. } => {
+                    remove_target(self, foster, target);
+                    remove_foster(self, foster, target);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+    use crate::component::TableStorage;
+
+    struct Likes;
+
+    impl Relation for Likes {
+        type Storage = TableStorage;
+    }
+
+    fn sorted(mut issues: Vec<RelationIssue>) -> Vec<RelationIssue> {
+        issues.sort_by_key(|issue| format!("{issue:?}"));
+        issues
+    }
+
+    #[test]
+    fn check_and_repair() {
+        let mut world = World::new();
+        let [a, b, c, d] = [(); 4].map(|_| world.spawn_empty().id());
+        let relation = TypeId::of::<Storage<Likes>>();
+
+        world.set_relations_batch([(a, b, Likes), (b, c, Likes), (c, d, Likes)]);
+        assert!(world.check_relation::<Likes>().is_empty());
+
+        // Break every invariant by hand.
+        world.despawn(d);
+        world
+            .get_mut::<Edges>(b)
+            .unwrap()
+            .fosters
+            .get_mut(&relation)
+            .unwrap()
+            .remove(&a);
+        world
+            .get_mut::<Edges>(a)
+            .unwrap()
+            .fosters
+            .entry(relation)
+            .or_default()
+            .insert(c);
+        world.get_mut::<Storage<Likes>>(b).unwrap().values.clear();
+
+        let stale = world.spawn_empty().id();
+        world
+            .get_mut::<Edges>(c)
+            .unwrap()
+            .fosters
+            .entry(relation)
+            .or_default()
+            .insert(stale);
+        world.despawn(stale);
+
+        let issues = world.check_relation::<Likes>();
+        assert_eq!(
+            sorted(issues.clone()),
+            sorted(vec![
+                RelationIssue::DespawnedTarget {
+                    foster: c,
+                    target: d
+                },
+                RelationIssue::DespawnedFoster {
+                    foster: stale,
+                    target: c
+                },
+                RelationIssue::MissingFoster {
+                    foster: a,
+                    target: b
+                },
+                RelationIssue::MissingTarget {
+                    foster: c,
+                    target: a
+                },
+                RelationIssue::StorageOutOfBounds {
+                    foster: b,
+                    target: c,
+                    index: 0
+                },
+            ])
+        );
+
+        world.repair_relation::<Likes>(&issues);
+
+        assert!(world.check_relation::<Likes>().is_empty());
+        assert!(world.get::<Edges>(b).unwrap().fosters[&relation].contains(&a));
+        assert!(world
+            .get::<Edges>(b)
+            .unwrap()
+            .targets_of::<Likes>()
+            .next()
+            .is_none());
+    }
+}
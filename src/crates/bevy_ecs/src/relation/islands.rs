@@ -0,0 +1,438 @@
+use super::*;
+use crate::{
+    change_detection::{DetectChangesMut, Mut},
+    query::ReadOnlyWorldQuery,
+    system::Resource,
+};
+use std::collections::VecDeque;
+
+// Islands are the connected components of a relation when edges are followed in both
+// directions. `islands` walks `Edges` on every call. Relations that are queried often can be
+// tracked in `Islands` instead which keeps an id per island. Additions merge the smaller island
+// into the larger one while removals and despawns only recompute the island they touched.
+// Entities without edges of the relation belong to no island.
+
+fn neighbors_erased(edges: &Edges, relation: TypeId) -> impl '_ + Iterator<Item = Entity> {
+    edges
+        .targets
+        .iter()
+        .filter_map(move |targets| targets.get(&relation))
+        .flat_map(|targets| targets.keys().copied())
+        .chain(edges.fosters.get(&relation).into_iter().flatten().copied())
+}
+
+// Breadth first walk of the island containing `from`. Empty if `from` has no edges.
+fn island<'a>(
+    get_edges: impl Fn(Entity) -> Option<&'a Edges>,
+    relation: TypeId,
+    from: Entity,
+) -> Vec<Entity> {
+    let mut island = Vec::new();
+    let mut visited = HashSet::from_iter([from]);
+    let mut queue = VecDeque::from([from]);
+
+    while let Some(entity) = queue.pop_front() {
+        let Some(edges) = get_edges(entity) else {
+            continue;
+        };
+
+        let mut neighbors = neighbors_erased(edges, relation)
+            .filter(|neighbor| get_edges(*neighbor).is_some())
+            .peekable();
+
+        if neighbors.peek().is_none() {
+            continue;
+        }
+
+        island.push(entity);
+        queue.extend(neighbors.filter(|neighbor| visited.insert(*neighbor)));
+    }
+
+    island
+}
+
+fn islands<'a>(
+    get_edges: impl Fn(Entity) -> Option<&'a Edges>,
+    relation: TypeId,
+    entities: impl IntoIterator<Item = Entity>,
+) -> Vec<Vec<Entity>> {
+    let mut visited = HashSet::<Entity>::new();
+    let mut islands = Vec::new();
+
+    for entity in entities {
+        if visited.contains(&entity) {
+            continue;
+        }
+
+        let island = island(&get_edges, relation, entity);
+
+        if !island.is_empty() {
+            visited.extend(island.iter().copied());
+            islands.push(island);
+        }
+    }
+
+    islands
+}
+
+impl World {
+    /// Partitions every entity with an `R` edge into islands of entities connected through `R`
+    /// in either direction.
+    pub fn islands<R: Relation>(&self) -> Vec<Vec<Entity>> {
+        islands(
+            |e| self.get::<Edges>(e),
+            TypeId::of::<Storage<R>>(),
+            self.iter_entities().map(|entity| entity.id()),
+        )
+    }
+
+    /// Returns the island of `entity` or nothing if it has no `R` edges.
+    pub fn island<R: Relation>(&self, entity: Entity) -> Vec<Entity> {
+        island(|e| self.get::<Edges>(e), TypeId::of::<Storage<R>>(), entity)
+    }
+
+    /// Starts maintaining island ids of `R` in the [`Islands`] resource.
+    pub fn track_islands<R: Relation>(&mut self) {
+        let relation = TypeId::of::<Storage<R>>();
+        let mut ids = IslandIds::default();
+
+        for island in self.islands::<R>() {
+            ids.insert(island);
+        }
+
+        self.get_resource_or_insert_with(Islands::default)
+            .relations
+            .insert(relation, ids);
+    }
+}
+
+impl<F: ReadOnlyWorldQuery> Query<'_, '_, &Edges, F> {
+    /// Partitions every entity with an `R` edge into islands of entities connected through `R`
+    /// in either direction. Only entities matched by this query are walked.
+    pub fn islands<R: Relation>(&self) -> Vec<Vec<Entity>> {
+        let relation = TypeId::of::<Storage<R>>();
+        let entities = self
+            .iter()
+            .flat_map(|edges| neighbors_erased(edges, relation))
+            .collect::<Vec<_>>();
+
+        islands(|e| self.get(e).ok(), relation, entities)
+    }
+
+    /// Returns the island of `entity` or nothing if it has no `R` edges.
+    /// Only entities matched by this query are walked.
+    pub fn island<R: Relation>(&self, entity: Entity) -> Vec<Entity> {
+        island(|e| self.get(e).ok(), TypeId::of::<Storage<R>>(), entity)
+    }
+}
+
+#[derive(Default)]
+struct IslandIds {
+    ids: HashMap<Entity, usize>,
+    members: HashMap<usize, Vec<Entity>>,
+    next: usize,
+}
+
+impl IslandIds {
+    fn insert(&mut self, island: Vec<Entity>) -> usize {
+        let id = self.next;
+        self.next += 1;
+        self.assign(id, island);
+        id
+    }
+
+    fn assign(&mut self, id: usize, island: Vec<Entity>) {
+        for entity in island.iter() {
+            self.ids.insert(*entity, id);
+        }
+        self.members.insert(id, island);
+    }
+
+    // Splits the island `id` into the islands that are left after an edge was removed.
+    // The largest part keeps the id. The walk stays within the old members since an exclusive
+    // overwrite has already written the new target, whose island is merged afterwards.
+    fn split(&mut self, world: &World, relation: TypeId, id: usize) {
+        let Some(members) = self.members.remove(&id) else {
+            return;
+        };
+
+        for entity in members.iter() {
+            self.ids.remove(entity);
+        }
+
+        let old_members = members.iter().copied().collect::<HashSet<_>>();
+        let get_edges = |e| world.get::<Edges>(e).filter(|_| old_members.contains(&e));
+        let mut parts = islands(get_edges, relation, members);
+        parts.sort_by_key(|part| std::cmp::Reverse(part.len()));
+
+        let mut parts = parts.into_iter();
+
+        if let Some(part) = parts.next() {
+            self.assign(id, part);
+        }
+
+        for part in parts {
+            self.insert(part);
+        }
+    }
+}
+
+/// Island ids of the relations registered with [`World::track_islands`].
+/// Ids of removed islands are not reused.
+#[derive(Resource, Default)]
+pub struct Islands {
+    relations: HashMap<TypeId, IslandIds>,
+}
+
+impl Islands {
+    pub fn is_tracked<R: Relation>(&self) -> bool {
+        self.relations.contains_key(&TypeId::of::<Storage<R>>())
+    }
+
+    /// # Panics
+    ///
+    /// Panics if `R` is not tracked.
+    pub fn island<R: Relation>(&self, entity: Entity) -> Option<usize> {
+        self.ids::<R>().ids.get(&entity).copied()
+    }
+
+    /// # Panics
+    ///
+    /// Panics if `R` is not tracked.
+    pub fn entities<R: Relation>(&self, island: usize) -> impl '_ + Iterator<Item = Entity> {
+        self.ids::<R>()
+            .members
+            .get(&island)
+            .into_iter()
+            .flatten()
+            .copied()
+    }
+
+    /// # Panics
+    ///
+    /// Panics if `R` is not tracked.
+    pub fn islands<R: Relation>(&self) -> impl '_ + Iterator<Item = usize> {
+        self.ids::<R>().members.keys().copied()
+    }
+
+    fn ids<R: Relation>(&self) -> &IslandIds {
+        self.relations
+            .get(&TypeId::of::<Storage<R>>())
+            .expect("Relation islands should be tracked")
+    }
+}
+
+fn update_islands(
+    world: &mut World,
+    relation: Option<TypeId>,
+    mut func: impl FnMut(&World, &mut IslandIds, TypeId),
+) {
+    if !world.contains_resource::<Islands>() {
+        return;
+    }
+
+    world.resource_scope(|world, mut islands: Mut<Islands>| {
+        let islands = islands.bypass_change_detection();
+        let relations = match relation {
+            Some(relation) => vec![relation],
+            None => islands.relations.keys().copied().collect(),
+        };
+
+        for relation in relations {
+            if let Some(ids) = islands.relations.get_mut(&relation) {
+                func(world, ids, relation);
+            }
+        }
+    });
+}
+
+pub(crate) fn islands_insert(world: &mut World, relation: TypeId, foster: Entity, target: Entity) {
+    update_islands(world, Some(relation), |_, ids, _| {
+        let island = |ids: &mut IslandIds, entity| match ids.ids.get(&entity) {
+            Some(id) => *id,
+            None => ids.insert(vec![entity]),
+        };
+
+        let (a, b) = (island(ids, foster), island(ids, target));
+
+        if a == b {
+            return;
+        }
+
+        let (keep, merge) = if ids.members[&a].len() >= ids.members[&b].len() {
+            (a, b)
+        } else {
+            (b, a)
+        };
+
+        let merged = ids.members.remove(&merge).unwrap_or_default();
+
+        for entity in merged.iter() {
+            ids.ids.insert(*entity, keep);
+        }
+
+        ids.members.entry(keep).or_default().extend(merged);
+    });
+}
+
+pub(crate) fn islands_remove(world: &mut World, relation: TypeId, foster: Entity) {
+    update_islands(world, Some(relation), |world, ids, relation| {
+        if let Some(id) = ids.ids.get(&foster).copied() {
+            ids.split(world, relation, id);
+        }
+    });
+}
+
+pub(crate) fn islands_despawn(world: &mut World, entity: Entity) {
+    update_islands(world, None, |world, ids, relation| {
+        if let Some(id) = ids.ids.get(&entity).copied() {
+            ids.split(world, relation, id);
+        }
+    });
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+    use crate::{component::TableStorage, system::SystemState};
+
+    struct Wire;
+
+    impl Relation for Wire {
+        type Storage = TableStorage;
+    }
+
+    struct PluggedInto;
+
+    impl Relation for PluggedInto {
+        type Storage = TableStorage;
+        const EXCLUSIVE: bool = true;
+    }
+
+    fn sorted(mut islands: Vec<Vec<Entity>>) -> Vec<Vec<Entity>> {
+        islands.iter_mut().for_each(|island| island.sort());
+        islands.sort();
+        islands
+    }
+
+    #[test]
+    fn connected_islands() {
+        let mut world = World::new();
+        let [a, b, c, d, e, f] = [(); 6].map(|_| world.spawn_empty().id());
+
+        world.set_relations_batch([(a, b, Wire), (c, b, Wire), (d, e, Wire)]);
+
+        assert_eq!(
+            sorted(world.islands::<Wire>()),
+            vec![vec![a, b, c], vec![d, e]]
+        );
+        assert_eq!(sorted(vec![world.island::<Wire>(c)]), vec![vec![a, b, c]]);
+        assert!(world.island::<Wire>(f).is_empty());
+
+        let mut system_state = SystemState::<Query<&Edges>>::new(&mut world);
+        let edges = system_state.get(&world);
+        assert_eq!(
+            sorted(edges.islands::<Wire>()),
+            vec![vec![a, b, c], vec![d, e]]
+        );
+    }
+
+    #[test]
+    fn tracked_islands() {
+        let mut world = World::new();
+        let [a, b, c, d, e] = [(); 5].map(|_| world.spawn_empty().id());
+
+        world.set_relations_batch([(a, b, Wire), (b, c, Wire)]);
+        world.track_islands::<Wire>();
+
+        let check = |world: &World| {
+            let islands = world.resource::<Islands>();
+            let tracked = islands
+                .islands::<Wire>()
+                .map(|island| islands.entities::<Wire>(island).collect())
+                .collect();
+
+            assert_eq!(sorted(tracked), sorted(world.islands::<Wire>()));
+
+            for entity in [a, b, c, d, e] {
+                let island = islands.island::<Wire>(entity);
+                assert_eq!(island.is_some(), !world.island::<Wire>(entity).is_empty());
+            }
+        };
+
+        check(&world);
+
+        let id = world.resource::<Islands>().island::<Wire>(a);
+
+        Set {
+            foster: d,
+            target: e,
+            relation: Wire,
+        }
+        .write(&mut world);
+
+        check(&world);
+
+        // Joining two islands keeps the id of the larger one.
+        Set {
+            foster: e,
+            target: c,
+            relation: Wire,
+        }
+        .write(&mut world);
+
+        check(&world);
+        assert_eq!(world.resource::<Islands>().island::<Wire>(d), id);
+
+        UnSet::<Wire> {
+            foster: b,
+            target: c,
+            _phantom: PhantomData,
+        }
+        .write(&mut world);
+
+        check(&world);
+        assert_ne!(
+            world.resource::<Islands>().island::<Wire>(a),
+            world.resource::<Islands>().island::<Wire>(c),
+        );
+
+        CheckedDespawn { entity: e }.write(&mut world);
+
+        check(&world);
+        assert_eq!(world.resource::<Islands>().island::<Wire>(d), None);
+        assert_eq!(world.resource::<Islands>().islands::<Wire>().count(), 1);
+    }
+
+    #[test]
+    fn exclusive_overwrite_into_other_island() {
+        let mut world = World::new();
+        let [lamp, socket, other, strip] = [(); 4].map(|_| world.spawn_empty().id());
+
+        world.set_relations_batch([(lamp, socket, PluggedInto), (strip, other, PluggedInto)]);
+        world.track_islands::<PluggedInto>();
+
+        // The lamp moves to the island of the strip and leaves the socket alone.
+        Set {
+            foster: lamp,
+            target: other,
+            relation: PluggedInto,
+        }
+        .write(&mut world);
+
+        let islands = world.resource::<Islands>();
+        let id = islands.island::<PluggedInto>(lamp);
+        assert!(id.is_some());
+        assert_eq!(islands.island::<PluggedInto>(strip), id);
+        assert_eq!(islands.island::<PluggedInto>(socket), None);
+        assert_eq!(islands.islands::<PluggedInto>().count(), 1);
+
+        let mut members = islands
+            .entities::<PluggedInto>(id.unwrap())
+            .collect::<Vec<_>>();
+        members.sort();
+        let mut expected = vec![lamp, other, strip];
+        expected.sort();
+        assert_eq!(members, expected);
+    }
+}
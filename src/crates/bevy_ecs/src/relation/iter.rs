@@ -0,0 +1,469 @@
+use super::*;
+use crate::{
+    entity::Entity,
+    query::{QueryIter, ROQueryItem, ReadOnlyWorldQuery, WorldQuery},
+    system::Query,
+};
+
+// Read-only counterparts of `Joinable` and `Attach`. Items borrow from the queries for `'o`
+// instead of from `self` which is what allows `Ops` to be consumed as a regular `Iterator`.
+pub trait JoinableRef<'o, Keys, Matches> {
+    type Out;
+
+    fn contains(&self, keys: Keys) -> Matches;
+    fn get_ref(&self, keys: Keys) -> Self::Out;
+}
+
+impl<'o, Q, F> JoinableRef<'o, Entity, bool> for &'o Query<'_, '_, Q, F>
+where
+    Q: 'static + WorldQuery,
+    F: 'static + ReadOnlyWorldQuery,
+{
+    type Out = ROQueryItem<'o, Q>;
+
+    fn contains(&self, entity: Entity) -> bool {
+        (**self).contains(entity)
+    }
+
+    fn get_ref(&self, entity: Entity) -> Self::Out {
+        let query: &'o Query<'_, '_, Q, F> = self;
+        query.get(entity).unwrap()
+    }
+}
+
+impl<'o, K0, M0, P0> JoinableRef<'o, (K0,), (M0,)> for (P0,)
+where
+    P0: JoinableRef<'o, K0, M0>,
+{
+    type Out = (P0::Out,);
+
+    fn contains(&self, (k0,): (K0,)) -> (M0,) {
+        (self.0.contains(k0),)
+    }
+
+    fn get_ref(&self, (k0,): (K0,)) -> Self::Out {
+        (self.0.get_ref(k0),)
+    }
+}
+
+impl<'o, K0, K1, M0, M1, P0, P1> JoinableRef<'o, (K0, K1), (M0, M1)> for (P0, P1)
+where
+    P0: JoinableRef<'o, K0, M0>,
+    P1: JoinableRef<'o, K1, M1>,
+{
+    type Out = (P0::Out, P1::Out);
+
+    fn contains(&self, (k0, k1): (K0, K1)) -> (M0, M1) {
+        (self.0.contains(k0), self.1.contains(k1))
+    }
+
+    fn get_ref(&self, (k0, k1): (K0, K1)) -> Self::Out {
+        (self.0.get_ref(k0), self.1.get_ref(k1))
+    }
+}
+
+pub trait AttachRef<'o, Keys, Items> {
+    type Out;
+    fn attach_ref(&self, keys: Keys, items: Items) -> Self::Out;
+}
+
+impl<'o, Items> AttachRef<'o, usize, Items> for Wiped {
+    type Out = Items;
+
+    fn attach_ref(&self, _index: usize, items: Items) -> Self::Out {
+        items
+    }
+}
+
+impl<'o, K, Items, T> AttachRef<'o, K, Items> for Option<T>
+where
+    T: AttachRef<'o, K, Items>,
+{
+    type Out = T::Out;
+
+    fn attach_ref(&self, keys: K, items: Items) -> Self::Out {
+        self.as_ref().unwrap().attach_ref(keys, items)
+    }
+}
+
+impl<'o, Items, R: Relation> AttachRef<'o, usize, Items> for StorageWorldQueryItem<'o, R> {
+    type Out = (&'o R, Items);
+
+    fn attach_ref(&self, index: usize, items: Items) -> Self::Out {
+        let storage: &'o Storage<R> = self.storage;
+        (storage.values.get(index).unwrap(), items)
+    }
+}
+
+impl<'o, K0, I0, P0> AttachRef<'o, (K0,), (I0,)> for (P0,)
+where
+    P0: AttachRef<'o, K0, I0>,
+{
+    type Out = (P0::Out,);
+
+    fn attach_ref(&self, (k0,): (K0,), (i0,): (I0,)) -> Self::Out {
+        (self.0.attach_ref(k0, i0),)
+    }
+}
+
+impl<'o, K0, K1, I0, I1, P0, P1> AttachRef<'o, (K0, K1), (I0, I1)> for (P0, P1)
+where
+    P0: AttachRef<'o, K0, I0>,
+    P1: AttachRef<'o, K1, I1>,
+{
+    type Out = (P0::Out, P1::Out);
+
+    fn attach_ref(&self, (k0, k1): (K0, K1), (i0, i1): (I0, I1)) -> Self::Out {
+        (self.0.attach_ref(k0, i0), self.1.attach_ref(k1, i1))
+    }
+}
+
+#[rustfmt::skip]
+type StorageFlat<'o, R, StorageComb> = <<StorageComb as Comb<RelationItem<'o, R>>>
+    ::Out as Flatten<()>>
+    ::Out;
+
+#[rustfmt::skip]
+type FosterIter<'o, 's, Q, R, F> = QueryIter<
+    'o,
+    's,
+    <(Q, Relations<R>) as WorldQuery>::ReadOnly,
+    <F as WorldQuery>::ReadOnly,
+>;
+
+struct Cursor<'o, Components, Storage> {
+    components: Components,
+    storage: Storage,
+    edges: &'o Edges,
+    outer: Targets<'o>,
+    inner: Option<((Entity, usize), Targets<'o>)>,
+}
+
+// Iterates the same permutations as `ForEachPermutations::for_each` for read-only `Ops`.
+// Components are cloned for every permutation of a foster so they have to be `Clone`.
+pub struct OpsIter<'o, Q, R, Joins, StorageComb, Edge, FosterItems>
+where
+    Q: 'static + WorldQuery,
+    R: 'static + RelationQuerySet,
+    Joins: Flatten<()>,
+    StorageComb: Comb<RelationItem<'o, R>>,
+    <StorageComb as Comb<RelationItem<'o, R>>>::Out: Flatten<()>,
+{
+    fosters: FosterItems,
+    joins: <Joins as Flatten<()>>::Out,
+    cursor: Option<Cursor<'o, ROQueryItem<'o, Q>, StorageFlat<'o, R, StorageComb>>>,
+    _phantom: PhantomData<Edge>,
+}
+
+impl<'o, Q, R, Joins, StorageComb, Edge, FosterItems>
+    OpsIter<'o, Q, R, Joins, StorageComb, Edge, FosterItems>
+where
+    Q: 'static + WorldQuery,
+    R: 'static + RelationQuerySet,
+    Joins: Flatten<()>,
+    StorageComb: Comb<RelationItem<'o, R>>,
+    <StorageComb as Comb<RelationItem<'o, R>>>::Out: Flatten<()>,
+    FosterItems: Iterator<Item = ROQueryItem<'o, (Q, Relations<R>)>>,
+{
+    pub(crate) fn new(fosters: FosterItems, joins: Joins) -> Self {
+        Self {
+            fosters,
+            joins: joins.flatten(()),
+            cursor: None,
+            _phantom: PhantomData,
+        }
+    }
+
+    fn advance<E: Relation>(&mut self) -> bool {
+        let Some((components, relations)) = self.fosters.next() else { return false };
+
+        self.cursor = Some(Cursor {
+            components,
+            storage: StorageComb::comb(relations.world_query).flatten(()),
+            edges: relations.edges,
+            outer: relations.edges.targets_of::<E>(),
+            inner: None,
+        });
+
+        true
+    }
+}
+
+impl<'o, E0, Q, R, Joins, StorageComb, FosterItems> Iterator
+    for OpsIter<'o, Q, R, Joins, StorageComb, (E0,), FosterItems>
+where
+    Q: 'static + WorldQuery,
+    R: 'static + RelationQuerySet,
+    E0: Relation,
+    Joins: Flatten<()>,
+    <Joins as Flatten<()>>::Out: JoinableRef<'o, (Entity,), (bool,)>,
+    StorageComb: Comb<RelationItem<'o, R>>,
+    <StorageComb as Comb<RelationItem<'o, R>>>::Out: Flatten<()>,
+    StorageFlat<'o, R, StorageComb>: AttachRef<
+        'o,
+        (usize,),
+        <<Joins as Flatten<()>>::Out as JoinableRef<'o, (Entity,), (bool,)>>::Out,
+    >,
+    ROQueryItem<'o, Q>: Clone,
+    FosterItems: Iterator<Item = ROQueryItem<'o, (Q, Relations<R>)>>,
+{
+    type Item = (
+        ROQueryItem<'o, Q>,
+        <StorageFlat<'o, R, StorageComb> as AttachRef<
+            'o,
+            (usize,),
+            <<Joins as Flatten<()>>::Out as JoinableRef<'o, (Entity,), (bool,)>>::Out,
+        >>::Out,
+    );
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(cursor) = &mut self.cursor {
+                for (e0, i0) in cursor.outer.by_ref() {
+                    let (m0,) = self.joins.contains((*e0,));
+                    if !m0 {
+                        continue;
+                    }
+                    return Some((
+                        cursor.components.clone(),
+                        cursor
+                            .storage
+                            .attach_ref((*i0,), self.joins.get_ref((*e0,))),
+                    ));
+                }
+            }
+
+            if !self.advance::<E0>() {
+                return None;
+            }
+        }
+    }
+}
+
+impl<'o, E0, E1, Q, R, Joins, StorageComb, FosterItems> Iterator
+    for OpsIter<'o, Q, R, Joins, StorageComb, (E0, E1), FosterItems>
+where
+    Q: 'static + WorldQuery,
+    R: 'static + RelationQuerySet,
+    E0: Relation,
+    E1: Relation,
+    Joins: Flatten<()>,
+    <Joins as Flatten<()>>::Out: JoinableRef<'o, (Entity, Entity), (bool, bool)>,
+    StorageComb: Comb<RelationItem<'o, R>>,
+    <StorageComb as Comb<RelationItem<'o, R>>>::Out: Flatten<()>,
+    StorageFlat<'o, R, StorageComb>: AttachRef<
+        'o,
+        (usize, usize),
+        <<Joins as Flatten<()>>::Out as JoinableRef<'o, (Entity, Entity), (bool, bool)>>::Out,
+    >,
+    ROQueryItem<'o, Q>: Clone,
+    FosterItems: Iterator<Item = ROQueryItem<'o, (Q, Relations<R>)>>,
+{
+    type Item = (
+        ROQueryItem<'o, Q>,
+        <StorageFlat<'o, R, StorageComb> as AttachRef<
+            'o,
+            (usize, usize),
+            <<Joins as Flatten<()>>::Out as JoinableRef<'o, (Entity, Entity), (bool, bool)>>::Out,
+        >>::Out,
+    );
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(cursor) = &mut self.cursor {
+                loop {
+                    let Some(((e0, i0), inner)) = &mut cursor.inner else {
+                        let Some((e0, i0)) = cursor.outer.next() else { break };
+                        cursor.inner = Some(((*e0, *i0), cursor.edges.targets_of::<E1>()));
+                        continue;
+                    };
+
+                    let Some((e1, i1)) = inner.next() else {
+                        cursor.inner = None;
+                        continue;
+                    };
+
+                    let (m0, m1) = self.joins.contains((*e0, *e1));
+                    if !m0 {
+                        cursor.inner = None;
+                        continue;
+                    }
+                    if !m1 {
+                        continue;
+                    }
+                    return Some((
+                        cursor.components.clone(),
+                        cursor
+                            .storage
+                            .attach_ref((*i0, *i1), self.joins.get_ref((*e0, *e1))),
+                    ));
+                }
+            }
+
+            if !self.advance::<E0>() {
+                return None;
+            }
+        }
+    }
+}
+
+#[rustfmt::skip]
+type EdgeFlat<R, EdgeComb> = <<EdgeComb as Comb<<R as RelationQuerySet>::Types>>
+    ::Out as Flatten<()>>
+    ::Out;
+
+impl<'o, 's, Q, R, F, Joins, EdgeComb, StorageComb> IntoIterator
+    for Ops<&'o Query<'_, 's, (Q, Relations<R>), F>, Joins, EdgeComb, StorageComb>
+where
+    Q: 'static + WorldQuery,
+    F: 'static + ReadOnlyWorldQuery,
+    R: 'static + RelationQuerySet,
+    EdgeComb: Comb<R::Types>,
+    <EdgeComb as Comb<R::Types>>::Out: Flatten<()>,
+    Joins: Flatten<()>,
+    StorageComb: Comb<RelationItem<'o, R>>,
+    <StorageComb as Comb<RelationItem<'o, R>>>::Out: Flatten<()>,
+    OpsIter<'o, Q, R, Joins, StorageComb, EdgeFlat<R, EdgeComb>, FosterIter<'o, 's, Q, R, F>>:
+        Iterator,
+{
+    type Item = <Self::IntoIter as Iterator>::Item;
+    type IntoIter =
+        OpsIter<'o, Q, R, Joins, StorageComb, EdgeFlat<R, EdgeComb>, FosterIter<'o, 's, Q, R, F>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        OpsIter::new(self.query.iter(), self.joins)
+    }
+}
+
+#[cfg(test)]
+#[allow(dead_code)]
+#[allow(unused_variables)]
+mod compile_tests {
+    use super::*;
+    use crate::prelude::*;
+
+    #[derive(Component)]
+    struct A;
+
+    #[derive(Relation)]
+    struct B;
+
+    #[derive(Relation)]
+    struct C;
+
+    #[derive(Component)]
+    struct D;
+
+    #[derive(Component)]
+    struct E;
+
+    fn iter_join(left: Query<(&A, Relations<(&B, &C)>)>, d: Query<&D>, e: Query<&E>) {
+        for (a, (d, (c, e))) in left.ops().join::<B>(&d).total_join::<C>(&e) {}
+    }
+
+    fn iter_join_optional(
+        left: Query<(&A, Relations<(Option<&B>, Option<&C>)>)>,
+        d: Query<&D>,
+        e: Query<&E>,
+    ) {
+        let joined: Vec<_> = left
+            .ops()
+            .join::<B>(&d)
+            .total_join::<C>(&e)
+            .into_iter()
+            .collect();
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+    use crate::{self as bevy_ecs, component::TableStorage, prelude::*};
+
+    fn run_system<Param, S: IntoSystem<(), (), Param>>(world: &mut World, system: S) {
+        let mut schedule = Schedule::default();
+        schedule.add_systems(system);
+        schedule.run(world);
+    }
+
+    #[derive(Component)]
+    struct Player(&'static str);
+
+    #[derive(Component)]
+    struct Item(&'static str, u32);
+
+    struct Holds(u32);
+
+    impl Relation for Holds {
+        type Storage = TableStorage;
+    }
+
+    fn setup(mut commands: Commands) {
+        let sword = commands.spawn(Item("sword", 10)).id();
+        let shield = commands.spawn(Item("shield", 25)).id();
+        let potion = commands.spawn(Item("potion", 1)).id();
+        let rock = commands.spawn(()).id();
+
+        let ann = commands.spawn(Player("ann")).id();
+        let ben = commands.spawn(Player("ben")).id();
+        commands.spawn(Player("cal"));
+
+        for (foster, target, count) in [
+            (ann, sword, 1),
+            (ann, potion, 3),
+            (ann, rock, 7),
+            (ben, shield, 1),
+            (ben, potion, 5),
+        ] {
+            commands.add(Set {
+                foster,
+                target,
+                relation: Holds(count),
+            });
+        }
+    }
+
+    fn aggregate(players: Query<(&Player, Relations<&Holds>)>, items: Query<&Item>) {
+        // Rocks are not items so the join skips them.
+        assert_eq!(players.ops().join::<Holds>(&items).into_iter().count(), 4);
+
+        let value = players
+            .ops()
+            .total_join::<Holds>(&items)
+            .into_iter()
+            .fold(0, |acc, (_, ((holds, item),))| acc + holds.0 * item.1);
+        assert_eq!(value, 10 + 3 + 25 + 5);
+
+        assert!(players
+            .ops()
+            .join::<Holds>(&items)
+            .into_iter()
+            .any(|(player, (item,))| player.0 == "ben" && item.0 == "shield"));
+
+        let (player, _) = players
+            .ops()
+            .join::<Holds>(&items)
+            .into_iter()
+            .find(|(_, (item,))| item.0 == "sword")
+            .unwrap();
+        assert_eq!(player.0, "ann");
+
+        let mut potions = players
+            .ops()
+            .total_join::<Holds>(&items)
+            .into_iter()
+            .filter_map(|(player, ((holds, item),))| {
+                (item.0 == "potion").then_some((player.0, holds.0))
+            })
+            .collect::<Vec<_>>();
+        potions.sort();
+        assert_eq!(potions, vec![("ann", 3), ("ben", 5)]);
+    }
+
+    #[test]
+    fn iter_test() {
+        let mut world = World::new();
+        run_system(&mut world, setup);
+        run_system(&mut world, aggregate);
+    }
+}
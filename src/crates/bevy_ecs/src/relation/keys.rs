@@ -0,0 +1,324 @@
+use super::{iter::*, joins::*};
+use crate::{
+    component::Component,
+    entity::Entity,
+    query::{QueryManyIter, ReadOnlyWorldQuery, WorldQuery},
+    system::Query,
+};
+use bevy_utils::HashMap;
+use std::hash::Hash;
+
+// Hash index from component values to entities.
+// Joining through a `KeyIndex` matches a target to every entity that shares the target's key
+// instead of just the target itself: `players.ops().join::<Near>(teams.on(&players_q))`.
+// The index can be rebuilt per call with `from_query` or maintained across frames with `update`.
+pub struct KeyIndex<K> {
+    keys: HashMap<Entity, K>,
+    entities: HashMap<K, Vec<Entity>>,
+}
+
+impl<K> Default for KeyIndex<K> {
+    fn default() -> Self {
+        Self {
+            keys: HashMap::default(),
+            entities: HashMap::default(),
+        }
+    }
+}
+
+impl<K: Component + Clone + Eq + Hash> KeyIndex<K> {
+    pub fn from_query<F: ReadOnlyWorldQuery>(query: &Query<(Entity, &K), F>) -> Self {
+        let mut index = Self::default();
+        for (entity, key) in query {
+            index.insert(entity, key.clone());
+        }
+        index
+    }
+
+    // Apply removals first so an entity that lost and regained its key in the same frame is kept.
+    pub fn update<F: ReadOnlyWorldQuery>(
+        &mut self,
+        changed: &Query<(Entity, &K), F>,
+        removed: impl IntoIterator<Item = Entity>,
+    ) {
+        for entity in removed {
+            self.remove(entity);
+        }
+
+        for (entity, key) in changed {
+            self.insert(entity, key.clone());
+        }
+    }
+
+    pub fn insert(&mut self, entity: Entity, key: K) {
+        if self.keys.get(&entity) == Some(&key) {
+            return;
+        }
+
+        self.remove(entity);
+        self.entities.entry(key.clone()).or_default().push(entity);
+        self.keys.insert(entity, key);
+    }
+
+    pub fn remove(&mut self, entity: Entity) -> Option<K> {
+        let key = self.keys.remove(&entity)?;
+
+        let entities = self
+            .entities
+            .get_mut(&key)
+            .expect("Indexed key should have entities");
+
+        entities.retain(|e| *e != entity);
+
+        if entities.is_empty() {
+            self.entities.remove(&key);
+        }
+
+        Some(key)
+    }
+
+    pub fn key(&self, entity: Entity) -> Option<&K> {
+        self.keys.get(&entity)
+    }
+
+    pub fn entities(&self, key: &K) -> &[Entity] {
+        self.entities.get(key).map_or(&[], Vec::as_slice)
+    }
+
+    pub fn peers(&self, entity: Entity) -> &[Entity] {
+        self.key(entity).map_or(&[], |key| self.entities(key))
+    }
+
+    pub fn on<Q>(&self, query: Q) -> KeyJoin<'_, K, Q> {
+        KeyJoin { index: self, query }
+    }
+}
+
+pub struct KeyJoin<'i, K, Q> {
+    index: &'i KeyIndex<K>,
+    query: Q,
+}
+
+impl<K, Q: Copy> Clone for KeyJoin<'_, K, Q> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<K, Q: Copy> Copy for KeyJoin<'_, K, Q> {}
+
+impl<'a, 'i, 's, K, Q, F> Joinable<'a, Entity, bool> for KeyJoin<'i, K, &'_ Query<'_, 's, Q, F>>
+where
+    K: Component + Clone + Eq + Hash,
+    Q: 'static + WorldQuery,
+    F: 'static + ReadOnlyWorldQuery,
+{
+    type Out = QueryManyIter<'a, 's, Q::ReadOnly, F::ReadOnly, std::slice::Iter<'i, Entity>>;
+
+    fn contains(&self, entity: Entity) -> bool {
+        let peers = self.index.peers(entity);
+        peers.iter().any(|peer| self.query.contains(*peer))
+    }
+
+    fn get(&'a mut self, entity: Entity) -> Self::Out {
+        self.query.iter_many(self.index.peers(entity))
+    }
+}
+
+// Mutable matches can alias across permutations so they are handed out as a lending iterator.
+// Use `fetch_next` to walk them.
+impl<'a, 'i, 's, K, Q, F> Joinable<'a, Entity, bool> for KeyJoin<'i, K, &'_ mut Query<'_, 's, Q, F>>
+where
+    K: Component + Clone + Eq + Hash,
+    Q: 'static + WorldQuery,
+    F: 'static + ReadOnlyWorldQuery,
+{
+    type Out = QueryManyIter<'a, 's, Q, F, std::slice::Iter<'i, Entity>>;
+
+    fn contains(&self, entity: Entity) -> bool {
+        let peers = self.index.peers(entity);
+        peers.iter().any(|peer| self.query.contains(*peer))
+    }
+
+    fn get(&'a mut self, entity: Entity) -> Self::Out {
+        self.query.iter_many_mut(self.index.peers(entity))
+    }
+}
+
+impl<'o, 'i: 'o, 'q: 'o, 's, K, Q, F> JoinableRef<'o, Entity, bool>
+    for KeyJoin<'i, K, &'q Query<'_, 's, Q, F>>
+where
+    K: Component + Clone + Eq + Hash,
+    Q: 'static + WorldQuery,
+    F: 'static + ReadOnlyWorldQuery,
+{
+    type Out = QueryManyIter<'o, 's, Q::ReadOnly, F::ReadOnly, std::slice::Iter<'o, Entity>>;
+
+    fn contains(&self, entity: Entity) -> bool {
+        let peers = self.index.peers(entity);
+        peers.iter().any(|peer| self.query.contains(*peer))
+    }
+
+    fn get_ref(&self, entity: Entity) -> Self::Out {
+        let query: &'o Query<'_, 's, Q, F> = self.query;
+        let index: &'o KeyIndex<K> = self.index;
+        query.iter_many(index.peers(entity))
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+    use crate::{
+        self as bevy_ecs, component::TableStorage, prelude::*, relation::*, system::SystemState,
+    };
+
+    fn run_system<Param, S: IntoSystem<(), (), Param>>(world: &mut World, system: S) {
+        let mut schedule = Schedule::default();
+        schedule.add_systems(system);
+        schedule.run(world);
+    }
+
+    #[derive(Component, Clone, Debug, PartialEq, Eq, Hash)]
+    struct Team(u8);
+
+    #[derive(Component)]
+    struct Name(&'static str);
+
+    #[derive(Component)]
+    struct Score(u32);
+
+    struct Targeting;
+
+    impl Relation for Targeting {
+        type Storage = TableStorage;
+    }
+
+    fn setup(mut commands: Commands) {
+        let ann = commands.spawn((Name("ann"), Team(0), Score(0))).id();
+        let ben = commands.spawn((Name("ben"), Team(0), Score(0))).id();
+        let cat = commands.spawn((Name("cat"), Team(1), Score(0))).id();
+        let dan = commands.spawn((Name("dan"), Team(1), Score(0))).id();
+        let eve = commands.spawn((Name("eve"), Team(2))).id();
+
+        for (foster, target) in [(ann, cat), (dan, ben), (ben, eve)] {
+            commands.add(Set {
+                foster,
+                target,
+                relation: Targeting,
+            });
+        }
+    }
+
+    fn read_join(
+        attackers: Query<(&Name, Relations<&Targeting>)>,
+        teams: Query<(Entity, &Team)>,
+        names: Query<&Name, With<Score>>,
+    ) {
+        let index = KeyIndex::from_query(&teams);
+        let mut found = vec![];
+
+        attackers
+            .ops()
+            .join::<Targeting>(index.on(&names))
+            .for_each(|attacker, (peers,)| {
+                for peer in peers {
+                    found.push((attacker.0, peer.0));
+                }
+            });
+
+        found.sort();
+        assert_eq!(
+            found,
+            vec![
+                ("ann", "cat"),
+                ("ann", "dan"),
+                ("dan", "ann"),
+                ("dan", "ben")
+            ]
+        );
+
+        let mut found = attackers
+            .ops()
+            .join::<Targeting>(index.on(&names))
+            .into_iter()
+            .flat_map(|(attacker, (peers,))| peers.map(move |peer| (attacker.0, peer.0)))
+            .collect::<Vec<_>>();
+
+        found.sort();
+        assert_eq!(
+            found,
+            vec![
+                ("ann", "cat"),
+                ("ann", "dan"),
+                ("dan", "ann"),
+                ("dan", "ben")
+            ]
+        );
+    }
+
+    fn write_join(
+        attackers: Query<(Entity, Relations<&Targeting>)>,
+        teams: Query<(Entity, &Team)>,
+        mut scores: Query<&mut Score>,
+    ) {
+        let index = KeyIndex::from_query(&teams);
+
+        attackers
+            .ops()
+            .join::<Targeting>(index.on(&mut scores))
+            .for_each(|_, (mut peers,)| {
+                while let Some(mut score) = peers.fetch_next() {
+                    score.0 += 1;
+                }
+            });
+    }
+
+    fn check_scores(scores: Query<(&Name, &Score)>) {
+        let mut found = scores
+            .iter()
+            .map(|(name, score)| (name.0, score.0))
+            .collect::<Vec<_>>();
+
+        found.sort();
+        assert_eq!(found, vec![("ann", 1), ("ben", 1), ("cat", 1), ("dan", 1)]);
+    }
+
+    #[test]
+    fn key_join() {
+        let mut world = World::new();
+        run_system(&mut world, setup);
+        run_system(&mut world, read_join);
+        run_system(&mut world, write_join);
+        run_system(&mut world, check_scores);
+    }
+
+    #[test]
+    fn key_index_update() {
+        let mut world = World::new();
+        let a = world.spawn(Team(0)).id();
+        let b = world.spawn(Team(0)).id();
+        let c = world.spawn(Team(1)).id();
+
+        let mut index = KeyIndex::default();
+
+        let mut system_state = SystemState::<(
+            Query<(Entity, &Team), Changed<Team>>,
+            RemovedComponents<Team>,
+        )>::new(&mut world);
+
+        let (changed, mut removed) = system_state.get(&world);
+        index.update(&changed, removed.iter());
+        assert_eq!(index.peers(a), &[a, b]);
+        assert_eq!(index.peers(c), &[c]);
+
+        world.entity_mut(b).insert(Team(1));
+        world.entity_mut(a).remove::<Team>();
+
+        let (changed, mut removed) = system_state.get(&world);
+        index.update(&changed, removed.iter());
+        assert_eq!(index.key(a), None);
+        assert!(index.entities(&Team(0)).is_empty());
+        assert_eq!(index.peers(c), &[c, b]);
+    }
+}
@@ -1,27 +1,68 @@
 use bevy_utils::{HashMap, HashSet};
 use core::any::TypeId;
+use indexmap::IndexMap;
 use smallvec::SmallVec;
 use std::marker::PhantomData;
 
 use crate as bevy_ecs;
+use bevy_utils::tracing::warn;
 
 use crate::{
-    component::{Component, ComponentStorage},
+    component::{Component, ComponentId, ComponentStorage},
     entity::Entity,
     query::{ReadOnlyWorldQuery, WorldQuery},
     system::Command,
     system::Query,
-    world::World,
+    world::{EntityMut, World},
 };
 
+mod closure;
+mod dot;
+mod error;
+mod filters;
+mod fosters;
+mod graph;
+mod hooks;
+mod integrity;
+mod islands;
+mod iter;
 mod joins;
+mod keys;
+mod order;
+mod parallel;
+mod pairs;
+mod paths;
 mod policies;
+#[cfg(feature = "bevy_reflect")]
+mod reflect;
+mod rules;
+mod search;
+mod state;
+mod topology;
 mod traversals;
 mod tuple_traits;
 
 pub use bevy_ecs_macros::Relation;
+pub use closure::*;
+pub use dot::*;
+pub use error::*;
+pub use filters::*;
+pub use fosters::*;
+pub use graph::*;
+use hooks::*;
+pub use integrity::*;
+pub use islands::*;
+pub use iter::*;
 pub use joins::*;
+pub use keys::*;
+pub use order::*;
+pub use parallel::*;
+pub use pairs::*;
+pub use paths::*;
 pub use policies::*;
+#[cfg(feature = "bevy_reflect")]
+pub use reflect::*;
+pub use rules::*;
 pub use traversals::*;
 pub use tuple_traits::*;
 
@@ -62,11 +103,34 @@ impl<R: Relation> Component for Storage<R> {
 
 #[derive(Component, Default)]
 pub struct Edges {
-    pub(crate) targets: [HashMap<TypeId, HashMap<Entity, usize>>; 4],
+    pub(crate) targets: [HashMap<TypeId, IndexMap<Entity, usize>>; 4],
     pub(crate) fosters: HashMap<TypeId, HashSet<Entity>>,
 }
 
+type Targets<'a> = std::iter::Flatten<std::option::IntoIter<&'a IndexMap<Entity, usize>>>;
+
 impl Edges {
+    fn targets_of<R: Relation>(&self) -> Targets<'_> {
+        self.targets[R::DESPAWN_POLICY as usize]
+            .get(&TypeId::of::<Storage<R>>())
+            .into_iter()
+            .flatten()
+    }
+
+    fn target_index<R: Relation>(&self, target: Entity) -> Option<usize> {
+        self.targets[R::DESPAWN_POLICY as usize]
+            .get(&TypeId::of::<Storage<R>>())?
+            .get(&target)
+            .copied()
+    }
+
+    /// Returns true if this entity was the target of `R` edges and all of them were removed.
+    pub fn lost_fosters<R: Relation>(&self) -> bool {
+        self.fosters
+            .get(&TypeId::of::<Storage<R>>())
+            .map_or(false, |fosters| fosters.is_empty())
+    }
+
     fn iter<R: Relation>(&self) -> impl '_ + Iterator<Item = (Entity, usize)> {
         self.targets[R::DESPAWN_POLICY as usize]
             .get(&TypeId::of::<Storage<R>>())
@@ -80,6 +144,15 @@ pub trait Relation: 'static + Sized + Send + Sync {
     type Storage: ComponentStorage;
     const DESPAWN_POLICY: DespawnPolicy = DespawnPolicy::Orphan;
     const EXCLUSIVE: bool = false;
+    const FRAGMENTING: bool = false;
+
+    /// Called after an edge from `foster` to `target` is set, by a command or when a despawn
+    /// policy reparents `target`.
+    fn on_set(_world: &mut World, _foster: Entity, _target: Entity) {}
+
+    /// Called after an edge from `foster` to `target` is removed while `foster` is alive,
+    /// including when `target` is despawned. `target` may no longer exist.
+    fn on_remove(_world: &mut World, _foster: Entity, _target: Entity) {}
 }
 
 #[derive(WorldQuery)]
@@ -130,6 +203,10 @@ impl<P0: RelationQuerySet, P1: RelationQuerySet> RelationQuerySet for (P0, P1) {
     type ColsWith<T: Default> = (P0::ColsWith<T>, P1::ColsWith<T>);
 }
 
+// Access is tracked per relation type: queries only ever read `Edges` and writes go to the
+// `Storage<R>` of each requested relation. `Edges` is written with exclusive `World` access, by
+// commands and by `World` methods such as `set_relations_batch`, never by queries. Systems over
+// disjoint relation types therefore never conflict in the executor.
 // TODO:
 // - Manual `WorldQuery` impl to get `ComponentId` from `World` to remove the usage of `TypeId`
 // - `TypeId` is not guarenteed to be stable which is a problem for serialization.
@@ -226,76 +303,250 @@ where
     R: Relation,
 {
     fn write(self, world: &mut World) {
-        let Some((mut foster_edges, mut foster_storage)) = world
-            .get_entity_mut(self.foster)
-            .map(|mut foster| (
-                foster.take::<Edges>().unwrap_or_default(),
-                foster.take::<Storage<R>>().unwrap_or_default()
-            ))
-        else {
-            // TODO: Logging
-            return
-        };
+        if let Err(error) = check_entities(world, self.foster, self.target) {
+            warn!("Could not set {}: {error}", std::any::type_name::<R>());
+            return;
+        }
 
-        let foster_indices = foster_edges.targets[R::DESPAWN_POLICY as usize]
-            .entry(TypeId::of::<Storage<R>>())
-            .or_default();
-
-        let mut exclusive_overwrite = None;
-
-        // TODO: Logging
-        if let Some(index) = foster_indices.get(&self.target) {
-            foster_storage.values[*index] = self.relation;
-        } else if let Some((old_target, index)) = foster_indices
-            .iter()
-            .next()
-            .map(|(target, index)| (*target, *index))
-            .filter(|_| R::EXCLUSIVE)
-        {
-            world
-                .get_entity_mut(old_target)
-                .expect("Foster should not have dangling entries")
-                .get_mut::<Edges>()
-                .expect("Edge component should exist")
-                .fosters
-                .get_mut(&TypeId::of::<Storage<R>>())
-                .expect("Target should have relation entry")
-                .remove(&self.foster);
-
-            foster_indices.clear();
-            foster_indices.insert(self.target, index);
-            foster_storage.values[index] = self.relation;
-            exclusive_overwrite = Some(old_target);
-        } else if let Some(mut target_edges) = world
-            .get_entity_mut(self.target)
-            .map(|mut target| target.take::<Edges>().unwrap_or_default())
-        {
-            foster_indices.insert(self.target, foster_storage.values.len());
-            foster_storage.values.push(self.relation);
-
-            target_edges
-                .fosters
-                .entry(TypeId::of::<Storage<R>>())
-                .or_default()
-                .insert(self.foster);
+        register_hooks::<R>(world);
+        init_components::<R>(&mut world.entity_mut(self.foster), true);
+        init_components::<R>(&mut world.entity_mut(self.target), false);
 
-            world
-                .get_entity_mut(self.target)
-                .unwrap()
-                .insert(target_edges);
-        }
+        let old_target = write_edge(world, self.foster, self.target, self.relation);
+        insert_pair::<R>(world, self.foster, self.target);
 
-        world
-            .get_entity_mut(self.foster)
-            .unwrap()
-            .insert((foster_edges, foster_storage));
+        // Overwriting an exclusive edge can shrink the closure, so it is recomputed.
+        match old_target {
+            Some(_) => closure_remove(world, TypeId::of::<Storage<R>>(), self.foster),
+            None => closure_insert(world, TypeId::of::<Storage<R>>(), self.foster, self.target),
+        }
 
-        if let Some(old_target) = exclusive_overwrite {
+        if let Some(old_target) = old_target {
+            R::on_remove(world, self.foster, old_target);
             R::DESPAWN_POLICY.apply(
                 world,
                 Operation::Delink(self.foster, TypeId::of::<Storage<R>>(), old_target),
             );
         }
+
+        R::on_set(world, self.foster, self.target);
+    }
+}
+
+pub struct SetBatch<R>
+where
+    R: Relation,
+{
+    pub edges: Vec<(Entity, Entity, R)>, // foster, target, relation
+}
+
+impl<R> Command for SetBatch<R>
+where
+    R: Relation,
+{
+    fn write(self, world: &mut World) {
+        let mut edges = self.edges;
+        edges.retain(|(foster, target, _)| {
+            let checked = check_entities(world, *foster, *target);
+            if let Err(error) = &checked {
+                warn!("Could not set {}: {error}", std::any::type_name::<R>());
+            }
+            checked.is_ok()
+        });
+
+        if edges.is_empty() {
+            return;
+        }
+
+        let mut fosters = HashSet::<Entity>::new();
+        let mut involved = HashSet::<Entity>::new();
+
+        for (foster, target, _) in edges.iter() {
+            fosters.insert(*foster);
+            involved.insert(*foster);
+            involved.insert(*target);
+        }
+
+        for entity in involved.iter() {
+            init_components::<R>(&mut world.entity_mut(*entity), fosters.contains(entity));
+        }
+
+        register_hooks::<R>(world);
+
+        let mut exclusive_overwrites = Vec::new();
+        let mut written = Vec::new();
+
+        for (foster, target, relation) in edges {
+            if let Some(old_target) = write_edge(world, foster, target, relation) {
+                exclusive_overwrites.push((foster, old_target));
+            }
+
+            written.push((foster, target));
+        }
+
+        closure_insert_batch(world, TypeId::of::<Storage<R>>(), &fosters);
+
+        for foster in fosters {
+            insert_pairs::<R>(world, foster);
+        }
+
+        for (foster, old_target) in exclusive_overwrites {
+            R::on_remove(world, foster, old_target);
+            R::DESPAWN_POLICY.apply(
+                world,
+                Operation::Delink(foster, TypeId::of::<Storage<R>>(), old_target),
+            );
+        }
+
+        for (foster, target) in written {
+            R::on_set(world, foster, target);
+        }
+    }
+}
+
+// Inserts whichever relation components are missing in a single archetype move.
+// Entities that already have them are left untouched so their change ticks are preserved.
+fn init_components<R: Relation>(entity_mut: &mut EntityMut, foster: bool) {
+    let missing_edges = !entity_mut.contains::<Edges>();
+    let missing_storage = foster && !entity_mut.contains::<Storage<R>>();
+
+    match (missing_edges, missing_storage) {
+        (true, true) => {
+            entity_mut.insert((Edges::default(), Storage::<R>::default()));
+        }
+        (true, false) => {
+            entity_mut.insert(Edges::default());
+        }
+        (false, true) => {
+            entity_mut.insert(Storage::<R>::default());
+        }
+        (false, false) => (),
+    }
+}
+
+// Writes an edge through `get_mut`. Both entities must have their relation components.
+// Returns the previous target if an exclusive edge was overwritten.
+fn write_edge<R: Relation>(
+    world: &mut World,
+    foster: Entity,
+    target: Entity,
+    relation: R,
+) -> Option<Entity> {
+    let mut foster_mut = world.entity_mut(foster);
+
+    let len = foster_mut
+        .get::<Storage<R>>()
+        .expect("Storage component should exist")
+        .values
+        .len();
+
+    let mut foster_edges = foster_mut
+        .get_mut::<Edges>()
+        .expect("Edge component should exist");
+
+    let foster_indices = foster_edges.targets[R::DESPAWN_POLICY as usize]
+        .entry(TypeId::of::<Storage<R>>())
+        .or_default();
+
+    let mut exclusive_overwrite = None;
+
+    let index = if let Some(index) = foster_indices.get(&target) {
+        Some(*index)
+    } else if let Some((old_target, index)) = foster_indices
+        .iter()
+        .next()
+        .map(|(target, index)| (*target, *index))
+        .filter(|_| R::EXCLUSIVE)
+    {
+        foster_indices.clear();
+        foster_indices.insert(target, index);
+        exclusive_overwrite = Some(old_target);
+        Some(index)
+    } else {
+        foster_indices.insert(target, len);
+        None
+    };
+
+    let mut foster_storage = foster_mut
+        .get_mut::<Storage<R>>()
+        .expect("Storage component should exist");
+
+    match index {
+        Some(index) => foster_storage.values[index] = relation,
+        None => foster_storage.values.push(relation),
+    }
+
+    if let Some(old_target) = exclusive_overwrite {
+        world
+            .get_mut::<Edges>(old_target)
+            .expect("Foster should not have dangling entries")
+            .fosters
+            .get_mut(&TypeId::of::<Storage<R>>())
+            .expect("Target should have relation entry")
+            .remove(&foster);
+
+        remove_pair(world, foster, TypeId::of::<Storage<R>>(), old_target);
+        islands_remove(world, TypeId::of::<Storage<R>>(), foster);
+    }
+
+    islands_insert(world, TypeId::of::<Storage<R>>(), foster, target);
+
+    world
+        .get_mut::<Edges>(target)
+        .expect("Edge component should exist")
+        .fosters
+        .entry(TypeId::of::<Storage<R>>())
+        .or_default()
+        .insert(foster);
+
+    exclusive_overwrite
+}
+
+impl World {
+    /// Sets many edges of relation `R` at once. Every entity is moved between archetypes at
+    /// most once to gain its relation components, which makes it well suited to building large
+    /// graphs. Fosters of [`Relation::FRAGMENTING`] relations move once more to gain all of their
+    /// new pairs together, and once for every exclusive edge they had before the batch that is
+    /// overwritten.
+    /// Edges whose foster or target does not exist are skipped.
+    pub fn set_relations_batch<R, I>(&mut self, edges: I)
+    where
+        R: Relation,
+        I: IntoIterator<Item = (Entity, Entity, R)>,
+    {
+        SetBatch {
+            edges: edges.into_iter().collect(),
+        }
+        .write(self);
+    }
+
+    /// Returns true if `component_id` is one of the components or resources relations keep their
+    /// edges in: [`Edges`], the values of a relation, a pair of a [`Relation::FRAGMENTING`]
+    /// relation, and resources such as [`Closures`] and [`Islands`]. These aren't reflected, tools
+    /// that copy entities should skip them and copy edges through `ReflectRelation` instead.
+    pub fn is_relation_data(&self, component_id: ComponentId) -> bool {
+        let Some(info) = self.components().get_info(component_id) else {
+            return false;
+        };
+
+        match info.type_id() {
+            Some(type_id) => {
+                [
+                    TypeId::of::<Edges>(),
+                    TypeId::of::<RelationHooks>(),
+                    TypeId::of::<Pairs>(),
+                    TypeId::of::<Closures>(),
+                    TypeId::of::<Islands>(),
+                ]
+                .contains(&type_id)
+                    || self
+                        .get_resource::<RelationHooks>()
+                        .map_or(false, |hooks| hooks.contains(type_id))
+            }
+            None => self
+                .get_resource::<Pairs>()
+                .map_or(false, |pairs| pairs.contains_id(component_id)),
+        }
     }
 }
 
@@ -313,31 +564,69 @@ where
     R: Relation,
 {
     fn write(self, world: &mut World) {
-        if world
-            .get_mut::<Edges>(self.foster)
-            .map_or(false, |mut edges| {
-                edges.targets[R::DESPAWN_POLICY as usize]
-                    .get_mut(&TypeId::of::<Storage<R>>())
-                    .and_then(|indices| indices.remove(&self.target))
-                    .is_some()
-            })
-        {
-            world
-                .get_mut::<Edges>(self.target)
-                .expect("Edge component should exist")
-                .fosters
-                .get_mut(&TypeId::of::<Storage<R>>())
-                .expect("Target should have relation entry")
-                .remove(&self.foster);
-
+        if remove_edge::<R>(world, self.foster, self.target) {
             R::DESPAWN_POLICY.apply(
                 world,
                 Operation::Delink(self.foster, TypeId::of::<Storage<R>>(), self.target),
             );
+        } else {
+            let error = RelationError::MissingEdge {
+                foster: self.foster,
+                target: self.target,
+            };
+            warn!("Could not unset {}: {error}", std::any::type_name::<R>());
         }
     }
 }
 
+/// Removes an edge without applying the despawn policy of `R` to the target.
+/// Does nothing if there is no such edge.
+pub struct Detach<R>
+where
+    R: Relation,
+{
+    pub foster: Entity,
+    pub target: Entity,
+    pub _phantom: PhantomData<R>,
+}
+
+impl<R> Command for Detach<R>
+where
+    R: Relation,
+{
+    fn write(self, world: &mut World) {
+        remove_edge::<R>(world, self.foster, self.target);
+    }
+}
+
+// Returns false if there was no edge to remove. `shift_remove` keeps the target order, at a
+// cost linear in the number of later targets.
+fn remove_edge<R: Relation>(world: &mut World, foster: Entity, target: Entity) -> bool {
+    if !world.get_mut::<Edges>(foster).map_or(false, |mut edges| {
+        edges.targets[R::DESPAWN_POLICY as usize]
+            .get_mut(&TypeId::of::<Storage<R>>())
+            .and_then(|indices| indices.shift_remove(&target))
+            .is_some()
+    }) {
+        return false;
+    }
+
+    world
+        .get_mut::<Edges>(target)
+        .expect("Edge component should exist")
+        .fosters
+        .get_mut(&TypeId::of::<Storage<R>>())
+        .expect("Target should have relation entry")
+        .remove(&foster);
+
+    remove_pair(world, foster, TypeId::of::<Storage<R>>(), target);
+    closure_remove(world, TypeId::of::<Storage<R>>(), foster);
+    islands_remove(world, TypeId::of::<Storage<R>>(), foster);
+    R::on_remove(world, foster, target);
+
+    true
+}
+
 pub struct CheckedDespawn {
     pub entity: Entity,
 }
@@ -346,5 +635,233 @@ impl Command for CheckedDespawn {
     fn write(self, world: &mut World) {
         DespawnPolicy::RecursiveDespawn.apply(world, Operation::Despawn(self.entity));
         world.despawn(self.entity);
+        pairs_despawn(world, self.entity);
+        closure_despawn(world, self.entity);
+        islands_despawn(world, self.entity);
+    }
+}
+
+/// Despawns an entity and removes its edges without applying the despawn policies of its
+/// relations, so its targets are kept as with [`DespawnPolicy::Orphan`].
+pub struct OrphaningDespawn {
+    pub entity: Entity,
+}
+
+impl Command for OrphaningDespawn {
+    fn write(self, world: &mut World) {
+        orphan_despawn(world, self.entity);
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+    use crate::{
+        component::TableStorage,
+        system::{IntoSystem, Query, System},
+    };
+
+    #[derive(Component)]
+    struct Node;
+
+    struct Link(usize);
+
+    impl Relation for Link {
+        type Storage = TableStorage;
+    }
+
+    struct Other;
+
+    impl Relation for Other {
+        type Storage = TableStorage;
+    }
+
+    struct Exclusive;
+
+    impl Relation for Exclusive {
+        type Storage = TableStorage;
+        const EXCLUSIVE: bool = true;
+    }
+
+    fn targets<R: Relation>(world: &World, entity: Entity) -> Vec<Entity> {
+        let mut targets = world
+            .get::<Edges>(entity)
+            .unwrap()
+            .iter::<R>()
+            .map(|(target, _)| target)
+            .collect::<Vec<_>>();
+        targets.sort();
+        targets
+    }
+
+    fn edges_added(world: &World, entity: Entity) -> u32 {
+        world
+            .entity(entity)
+            .get_change_ticks::<Edges>()
+            .unwrap()
+            .added
+            .get()
+    }
+
+    #[test]
+    fn batch_chain() {
+        let mut world = World::new();
+        let nodes = world
+            .spawn_batch((0..100).map(|_| Node))
+            .collect::<Vec<_>>();
+
+        world.set_relations_batch(
+            nodes
+                .windows(2)
+                .enumerate()
+                .map(|(n, pair)| (pair[0], pair[1], Link(n))),
+        );
+
+        for (n, pair) in nodes.windows(2).enumerate() {
+            let edges = world.get::<Edges>(pair[0]).unwrap();
+            let (target, index) = edges.iter::<Link>().next().unwrap();
+            assert_eq!(target, pair[1]);
+            assert_eq!(
+                world.get::<Storage<Link>>(pair[0]).unwrap().values[index].0,
+                n
+            );
+
+            let fosters = world
+                .get::<Edges>(pair[1])
+                .unwrap()
+                .fosters
+                .get(&TypeId::of::<Storage<Link>>())
+                .unwrap();
+            assert!(fosters.len() == 1 && fosters.contains(&pair[0]));
+        }
+
+        assert!(world.get::<Storage<Link>>(nodes[99]).is_none());
+    }
+
+    #[test]
+    fn batch_overwrite() {
+        let mut world = World::new();
+        let [a, b, c] = [(); 3].map(|_| world.spawn_empty().id());
+
+        world.set_relations_batch([(a, b, Link(0)), (a, c, Link(1)), (a, b, Link(2))]);
+        assert_eq!(targets::<Link>(&world, a), vec![b, c]);
+
+        let edges = world.get::<Edges>(a).unwrap();
+        let storage = world.get::<Storage<Link>>(a).unwrap();
+        let index = edges.targets[Link::DESPAWN_POLICY as usize]
+            .get(&TypeId::of::<Storage<Link>>())
+            .unwrap()[&b];
+        assert_eq!(storage.values[index].0, 2);
+
+        world.set_relations_batch([(a, b, Exclusive), (a, c, Exclusive)]);
+        assert_eq!(targets::<Exclusive>(&world, a), vec![c]);
+        assert!(world
+            .get::<Edges>(b)
+            .unwrap()
+            .fosters
+            .get(&TypeId::of::<Storage<Exclusive>>())
+            .map_or(true, |fosters| fosters.is_empty()));
+    }
+
+    #[test]
+    fn set_in_place() {
+        let mut world = World::new();
+        let [a, b, c] = [(); 3].map(|_| world.spawn_empty().id());
+
+        Set {
+            foster: a,
+            target: b,
+            relation: Link(0),
+        }
+        .write(&mut world);
+
+        let archetype = world.entity(a).archetype().id();
+        let added = edges_added(&world, a);
+        world.increment_change_tick();
+
+        Set {
+            foster: a,
+            target: c,
+            relation: Link(1),
+        }
+        .write(&mut world);
+
+        UnSet {
+            foster: a,
+            target: b,
+            _phantom: PhantomData::<Link>,
+        }
+        .write(&mut world);
+
+        assert_eq!(world.entity(a).archetype().id(), archetype);
+        assert_eq!(edges_added(&world, a), added);
+        assert_eq!(targets::<Link>(&world, a), vec![c]);
+    }
+
+    #[test]
+    fn batch_skips_missing() {
+        let mut world = World::new();
+        let a = world.spawn_empty().id();
+        let b = world.spawn_empty().id();
+        let missing = world.spawn_empty().id();
+        world.despawn(missing);
+
+        world.set_relations_batch([
+            (a, missing, Link(0)),
+            (missing, b, Link(1)),
+            (a, b, Link(2)),
+        ]);
+        assert_eq!(targets::<Link>(&world, a), vec![b]);
+        assert!(world.get_entity(missing).is_none());
+
+        // Nothing is registered when every edge is skipped.
+        world.set_relations_batch([(a, missing, Other)]);
+        assert!(world.component_id::<Storage<Other>>().is_none());
+        assert!(!world
+            .resource::<RelationHooks>()
+            .contains(TypeId::of::<Storage<Other>>()));
+    }
+
+    fn compatible<P0, P1>(
+        world: &mut World,
+        s0: impl IntoSystem<(), (), P0>,
+        s1: impl IntoSystem<(), (), P1>,
+    ) -> bool {
+        let mut s0 = IntoSystem::into_system(s0);
+        let mut s1 = IntoSystem::into_system(s1);
+        s0.initialize(world);
+        s1.initialize(world);
+        s0.update_archetype_component_access(world);
+        s1.update_archetype_component_access(world);
+        s0.component_access().is_compatible(s1.component_access())
+            && s0
+                .archetype_component_access()
+                .is_compatible(s1.archetype_component_access())
+    }
+
+    #[test]
+    fn relation_access() {
+        let mut world = World::new();
+        let [a, b] = [(); 2].map(|_| world.spawn_empty().id());
+        world.set_relations_batch([(a, b, Link(0))]);
+        world.set_relations_batch([(a, b, Other)]);
+
+        assert!(compatible(
+            &mut world,
+            |mut q: Query<Relations<&mut Link>>| for _ in q.iter_mut() {},
+            |mut q: Query<Relations<&mut Other>>| for _ in q.iter_mut() {},
+        ));
+
+        assert!(compatible(
+            &mut world,
+            |q: Query<Relations<&Link>>| for _ in q.iter() {},
+            |q: Query<Relations<(&Link, &Other)>>| for _ in q.iter() {},
+        ));
+
+        assert!(!compatible(
+            &mut world,
+            |mut q: Query<Relations<&mut Link>>| for _ in q.iter_mut() {},
+            |q: Query<Relations<(&Link, &Other)>>| for _ in q.iter() {},
+        ));
     }
 }
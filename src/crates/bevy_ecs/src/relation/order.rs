@@ -0,0 +1,256 @@
+use super::*;
+
+// Targets of a foster are kept in insertion order. Setting an existing edge again keeps its
+// position and removing an edge shifts the later targets down, so joins, traversals and
+// `RelationGraph` see targets in the same order every run. Storage indices are untouched by
+// reordering since only the order of the `Edges` entries changes.
+//
+// Shifting makes removing an edge linear in the number of targets that follow it in the
+// foster's order for that relation, removing the last target stays constant.
+
+/// Sets an edge like [`Set`] and moves `target` to `index` in the foster's target order.
+/// An `index` past the end moves `target` to the end.
+pub struct InsertAt<R>
+where
+    R: Relation,
+{
+    pub foster: Entity,
+    pub target: Entity,
+    pub relation: R,
+    pub index: usize,
+}
+
+impl<R> Command for InsertAt<R>
+where
+    R: Relation,
+{
+    fn write(self, world: &mut World) {
+        Set {
+            foster: self.foster,
+            target: self.target,
+            relation: self.relation,
+        }
+        .write(world);
+
+        move_target::<R>(world, self.foster, self.target, self.index);
+    }
+}
+
+/// Moves `target` to `index` in the foster's target order.
+/// An `index` past the end moves `target` to the end.
+pub struct MoveTo<R>
+where
+    R: Relation,
+{
+    pub foster: Entity,
+    pub target: Entity,
+    pub index: usize,
+    pub _phantom: PhantomData<R>,
+}
+
+impl<R> Command for MoveTo<R>
+where
+    R: Relation,
+{
+    fn write(self, world: &mut World) {
+        if !move_target::<R>(world, self.foster, self.target, self.index) {
+            let error = RelationError::MissingEdge {
+                foster: self.foster,
+                target: self.target,
+            };
+            warn!("Could not move {}: {error}", std::any::type_name::<R>());
+        }
+    }
+}
+
+/// Stably sorts the targets of `foster` by the key `key` extracts from each edge.
+pub struct SortByKey<R, F> {
+    pub foster: Entity,
+    pub key: F,
+    pub _phantom: PhantomData<R>,
+}
+
+impl<R, F, K> Command for SortByKey<R, F>
+where
+    R: Relation,
+    F: 'static + Send + FnMut(Entity, &R) -> K,
+    K: Ord,
+{
+    fn write(self, world: &mut World) {
+        sort_targets::<R, K>(world, self.foster, self.key);
+    }
+}
+
+// Returns false if there is no edge to move.
+fn move_target<R: Relation>(
+    world: &mut World,
+    foster: Entity,
+    target: Entity,
+    index: usize,
+) -> bool {
+    if !has_edge::<R>(world, foster, target) {
+        return false;
+    }
+
+    let mut edges = world
+        .get_mut::<Edges>(foster)
+        .expect("Edge component should exist");
+
+    let indices = edges.targets[R::DESPAWN_POLICY as usize]
+        .get_mut(&TypeId::of::<Storage<R>>())
+        .expect("Foster should have relation entry");
+
+    let from = indices
+        .get_index_of(&target)
+        .expect("Foster should have target entry");
+
+    let to = index.min(indices.len() - 1);
+    indices.move_index(from, to);
+
+    true
+}
+
+fn sort_targets<R: Relation, K: Ord>(
+    world: &mut World,
+    foster: Entity,
+    mut key: impl FnMut(Entity, &R) -> K,
+) {
+    let Some(foster_ref) = world.get_entity(foster) else {
+        return;
+    };
+
+    let (Some(edges), Some(storage)) = (foster_ref.get::<Edges>(), foster_ref.get::<Storage<R>>())
+    else {
+        return;
+    };
+
+    let mut sorted = edges
+        .iter::<R>()
+        .map(|(target, index)| (key(target, &storage.values[index]), target, index))
+        .collect::<Vec<_>>();
+
+    if sorted.is_empty() {
+        return;
+    }
+
+    sorted.sort_by(|(a, ..), (b, ..)| a.cmp(b));
+
+    world
+        .get_mut::<Edges>(foster)
+        .expect("Edge component should exist")
+        .targets[R::DESPAWN_POLICY as usize]
+        .insert(
+            TypeId::of::<Storage<R>>(),
+            sorted
+                .into_iter()
+                .map(|(_, target, index)| (target, index))
+                .collect(),
+        );
+}
+
+impl World {
+    /// Sets an edge of relation `R` and moves `target` to `index` in the target order of
+    /// `foster`. See [`InsertAt`].
+    pub fn insert_at<R: Relation>(
+        &mut self,
+        foster: Entity,
+        target: Entity,
+        relation: R,
+        index: usize,
+    ) {
+        InsertAt {
+            foster,
+            target,
+            relation,
+            index,
+        }
+        .write(self);
+    }
+
+    /// Moves `target` to `index` in the target order of relation `R` of `foster`.
+    /// See [`MoveTo`].
+    pub fn move_to<R: Relation>(&mut self, foster: Entity, target: Entity, index: usize) {
+        MoveTo::<R> {
+            foster,
+            target,
+            index,
+            _phantom: PhantomData,
+        }
+        .write(self);
+    }
+
+    /// Stably sorts the targets of relation `R` of `foster` by `key`.
+    pub fn sort_by_key<R: Relation, K: Ord>(
+        &mut self,
+        foster: Entity,
+        key: impl FnMut(Entity, &R) -> K,
+    ) {
+        sort_targets(self, foster, key);
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+    use crate::{component::TableStorage, system::SystemState};
+
+    struct Waypoint(u32);
+
+    impl Relation for Waypoint {
+        type Storage = TableStorage;
+    }
+
+    fn route(world: &mut World, foster: Entity) -> Vec<Entity> {
+        let mut system_state = SystemState::<RelationGraph<Waypoint>>::new(world);
+        let graph = system_state.get(world);
+        graph.neighbors(foster).collect()
+    }
+
+    #[test]
+    fn ordered_targets() {
+        let mut world = World::new();
+        let [ship, a, b, c, d] = [(); 5].map(|_| world.spawn_empty().id());
+
+        world.set_relations_batch([
+            (ship, c, Waypoint(3)),
+            (ship, a, Waypoint(1)),
+            (ship, b, Waypoint(2)),
+        ]);
+        assert_eq!(route(&mut world, ship), vec![c, a, b]);
+
+        // Setting an existing edge keeps its position.
+        world.set_relations_batch([(ship, c, Waypoint(0))]);
+        assert_eq!(route(&mut world, ship), vec![c, a, b]);
+
+        world.insert_at(ship, d, Waypoint(4), 1);
+        assert_eq!(route(&mut world, ship), vec![c, d, a, b]);
+
+        world.move_to::<Waypoint>(ship, c, usize::MAX);
+        assert_eq!(route(&mut world, ship), vec![d, a, b, c]);
+
+        world.sort_by_key(ship, |_, waypoint: &Waypoint| waypoint.0);
+        assert_eq!(route(&mut world, ship), vec![c, a, b, d]);
+
+        UnSet::<Waypoint> {
+            foster: ship,
+            target: a,
+            _phantom: PhantomData,
+        }
+        .write(&mut world);
+        assert_eq!(route(&mut world, ship), vec![c, b, d]);
+
+        let mut visited = Vec::new();
+        world
+            .relation_query::<Entity, Option<&Waypoint>>()
+            .ops(&world, |ops| {
+                ops.breadth_first::<Waypoint>(ship)
+                    .for_each(|entity, _| visited.push(*entity));
+            });
+        assert_eq!(visited, vec![ship, c, b, d]);
+
+        // Reordering doesn't touch the values.
+        let mut system_state = SystemState::<RelationGraph<Waypoint>>::new(&mut world);
+        let graph = system_state.get(&world);
+        assert_eq!(graph.edge_value(ship, d).unwrap().0, 4);
+    }
+}
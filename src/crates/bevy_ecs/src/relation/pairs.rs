@@ -0,0 +1,400 @@
+use super::*;
+use crate::{
+    archetype::Archetypes,
+    component::{ComponentDescriptor, ComponentId},
+    query::{ROQueryItem, ReadOnlyWorldQuery},
+    system::Resource,
+};
+use bevy_ptr::OwningPtr;
+use std::{alloc::Layout, ptr::NonNull};
+
+// Fragmenting relations give every `(R, target)` pair its own zero sized component on the foster.
+// Fosters of the same target share archetypes so looking them up only visits matching tables.
+// Edges are still stored in `Edges` and `Storage<R>` as for any other relation so joins,
+// traversals and despawn policies don't need to know about pairs.
+// Components can't be unregistered, so once a target is despawned and no foster has its pair
+// anymore the component id is kept aside and reused for the next target of the same relation.
+// The debug name of a reused component still names its first target.
+#[derive(Resource, Default)]
+pub struct Pairs {
+    ids: HashMap<(TypeId, Entity), ComponentId>,
+    relations: HashMap<Entity, Vec<TypeId>>,
+    free: HashMap<TypeId, Vec<ComponentId>>,
+}
+
+impl Pairs {
+    pub fn id<R: Relation>(&self, target: Entity) -> Option<ComponentId> {
+        self.get(TypeId::of::<Storage<R>>(), target)
+    }
+
+    // Fosters that have an `R` edge to `target`. Pass the result to `Query::iter_many`.
+    pub fn fosters<'a, R: Relation>(
+        &self,
+        archetypes: &'a Archetypes,
+        target: Entity,
+    ) -> impl 'a + Iterator<Item = Entity> {
+        let id = self.id::<R>(target);
+
+        archetypes
+            .iter()
+            .filter(move |archetype| id.map_or(false, |id| archetype.contains(id)))
+            .flat_map(|archetype| archetype.entities().iter().map(|entity| entity.entity()))
+    }
+
+    pub(crate) fn get(&self, relation: TypeId, target: Entity) -> Option<ComponentId> {
+        self.ids.get(&(relation, target)).copied()
+    }
+
+    pub(crate) fn contains_id(&self, id: ComponentId) -> bool {
+        self.ids.values().any(|pair| *pair == id)
+            || self.free.values().flatten().any(|pair| *pair == id)
+    }
+}
+
+fn init_pair<R: Relation>(world: &mut World, target: Entity) -> ComponentId {
+    let relation = TypeId::of::<Storage<R>>();
+    let mut pairs = world.get_resource_or_insert_with(Pairs::default);
+
+    if let Some(id) = pairs.get(relation, target) {
+        return id;
+    }
+
+    let id = match pairs.free.get_mut(&relation).and_then(Vec::pop) {
+        Some(id) => id,
+        None => {
+            // SAFETY: Pair components are zero sized and have nothing to drop.
+            let descriptor = unsafe {
+                ComponentDescriptor::new_with_layout(
+                    format!("Pair<{}, {:?}>", std::any::type_name::<R>(), target),
+                    <R::Storage as ComponentStorage>::STORAGE_TYPE,
+                    Layout::new::<()>(),
+                    None,
+                )
+            };
+            world.init_component_with_descriptor(descriptor)
+        }
+    };
+
+    let mut pairs = world.resource_mut::<Pairs>();
+    pairs.ids.insert((relation, target), id);
+    pairs.relations.entry(target).or_default().push(relation);
+    id
+}
+
+pub(crate) fn insert_pair<R: Relation>(world: &mut World, foster: Entity, target: Entity) {
+    if R::FRAGMENTING {
+        let id = init_pair::<R>(world, target);
+        insert_pair_by_id(world, foster, id);
+    }
+}
+
+// Inserts the pairs of every current `R` target of `foster` in a single archetype move.
+pub(crate) fn insert_pairs<R: Relation>(world: &mut World, foster: Entity) {
+    if !R::FRAGMENTING {
+        return;
+    }
+
+    let Some(targets) = world.get::<Edges>(foster).map(|edges| {
+        edges
+            .targets_of::<R>()
+            .map(|(target, _)| *target)
+            .collect::<Vec<_>>()
+    }) else {
+        return;
+    };
+
+    let mut ids = targets
+        .into_iter()
+        .map(|target| init_pair::<R>(world, target))
+        .collect::<Vec<_>>();
+
+    let mut foster_mut = world.entity_mut(foster);
+    ids.retain(|id| !foster_mut.contains_id(*id));
+    ids.sort();
+
+    if ids.is_empty() {
+        return;
+    }
+
+    // SAFETY: Every id was registered with the layout of `()` in this world. A dangling pointer
+    // is a valid pointer to a zero sized value.
+    unsafe {
+        foster_mut.insert_by_ids(
+            &ids,
+            ids.iter()
+                .map(|_| OwningPtr::new(NonNull::<()>::dangling().cast())),
+        );
+    }
+}
+
+// Frees the pairs of a despawned target for reuse once no foster has them.
+pub(crate) fn pairs_despawn(world: &mut World, target: Entity) {
+    let Some(relations) = world
+        .get_resource_mut::<Pairs>()
+        .and_then(|mut pairs| pairs.relations.remove(&target))
+    else {
+        return;
+    };
+
+    for relation in relations {
+        let id = world
+            .resource_mut::<Pairs>()
+            .ids
+            .remove(&(relation, target))
+            .expect("Pair should exist");
+
+        let in_use = world
+            .archetypes()
+            .iter()
+            .any(|archetype| !archetype.is_empty() && archetype.contains(id));
+
+        if !in_use {
+            world
+                .resource_mut::<Pairs>()
+                .free
+                .entry(relation)
+                .or_default()
+                .push(id);
+        }
+    }
+}
+
+// Used when despawn policies move an edge to a new foster without knowing the relation type.
+pub(crate) fn reinsert_pair(world: &mut World, foster: Entity, relation: TypeId, target: Entity) {
+    if let Some(id) = world
+        .get_resource::<Pairs>()
+        .and_then(|pairs| pairs.get(relation, target))
+    {
+        insert_pair_by_id(world, foster, id);
+    }
+}
+
+impl<'w, 's, Q, F> Query<'w, 's, Q, F>
+where
+    Q: WorldQuery,
+    F: ReadOnlyWorldQuery,
+{
+    /// Iterates the items of fosters with an edge of the fragmenting relation `R` to `target`.
+    /// Only the archetypes of the `(R, target)` pair that match the query are visited.
+    pub fn iter_pair<R: Relation>(
+        &self,
+        pairs: &Pairs,
+        target: Entity,
+    ) -> impl '_ + Iterator<Item = ROQueryItem<'_, Q>> {
+        let id = pairs.id::<R>(target);
+        let archetypes = self.world.archetypes();
+
+        self.state
+            .matched_archetype_ids
+            .iter()
+            .map(move |archetype_id| &archetypes[*archetype_id])
+            .filter(move |archetype| id.map_or(false, |id| archetype.contains(id)))
+            .flat_map(|archetype| archetype.entities())
+            .filter_map(|entity| self.get(entity.entity()).ok())
+    }
+}
+
+fn insert_pair_by_id(world: &mut World, foster: Entity, id: ComponentId) {
+    let Some(mut foster_mut) = world.get_entity_mut(foster) else {
+        return;
+    };
+
+    if !foster_mut.contains_id(id) {
+        OwningPtr::make((), |ptr| {
+            // SAFETY: `id` was registered with the layout of `()` in this world.
+            unsafe { foster_mut.insert_by_id(id, ptr) };
+        });
+    }
+}
+
+pub(crate) fn remove_pair(world: &mut World, foster: Entity, relation: TypeId, target: Entity) {
+    let Some(id) = world
+        .get_resource::<Pairs>()
+        .and_then(|pairs| pairs.get(relation, target))
+    else {
+        return;
+    };
+
+    if let Some(mut foster_mut) = world.get_entity_mut(foster) {
+        foster_mut.remove_by_id(id);
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+    use crate::{self as bevy_ecs, component::TableStorage, prelude::*, system::SystemState};
+
+    #[derive(Component)]
+    struct Name(&'static str);
+
+    #[derive(Component)]
+    struct Marker;
+
+    struct ChildOf;
+
+    impl Relation for ChildOf {
+        type Storage = TableStorage;
+        const EXCLUSIVE: bool = true;
+        const FRAGMENTING: bool = true;
+    }
+
+    struct Recursive;
+
+    impl Relation for Recursive {
+        type Storage = TableStorage;
+        const DESPAWN_POLICY: DespawnPolicy = DespawnPolicy::RecursiveDespawn;
+        const FRAGMENTING: bool = true;
+    }
+
+    fn child_of(world: &mut World, parent: Entity) -> Vec<&'static str> {
+        let mut system_state = SystemState::<(Res<Pairs>, &Archetypes, Query<&Name>)>::new(world);
+        let (pairs, archetypes, names) = system_state.get(world);
+
+        let mut found = names
+            .iter_many(pairs.fosters::<ChildOf>(archetypes, parent))
+            .map(|name| name.0)
+            .collect::<Vec<_>>();
+
+        found.sort();
+        found
+    }
+
+    #[test]
+    fn pair_archetypes() {
+        let mut world = World::new();
+        world.init_resource::<Pairs>();
+
+        let a = world.spawn(Name("a")).id();
+        let b = world.spawn(Name("b")).id();
+        let x = world.spawn(Name("x")).id();
+        let y = world.spawn(Name("y")).id();
+        let z = world.spawn(Name("z")).id();
+
+        world.set_relations_batch([(x, a, ChildOf), (y, a, ChildOf), (z, b, ChildOf)]);
+
+        assert_eq!(child_of(&mut world, a), vec!["x", "y"]);
+        assert_eq!(child_of(&mut world, b), vec!["z"]);
+
+        let pairs = world.resource::<Pairs>();
+        let (pair_a, pair_b) = (
+            pairs.id::<ChildOf>(a).unwrap(),
+            pairs.id::<ChildOf>(b).unwrap(),
+        );
+        assert_eq!(
+            world.entity(x).archetype().id(),
+            world.entity(y).archetype().id()
+        );
+        assert!(world.entity(z).contains_id(pair_b));
+
+        // Exclusive overwrite moves `y` to the archetype of `b`.
+        Set {
+            foster: y,
+            target: b,
+            relation: ChildOf,
+        }
+        .write(&mut world);
+
+        assert!(!world.entity(y).contains_id(pair_a));
+        assert_eq!(child_of(&mut world, a), vec!["x"]);
+        assert_eq!(child_of(&mut world, b), vec!["y", "z"]);
+
+        UnSet::<ChildOf> {
+            foster: z,
+            target: b,
+            _phantom: PhantomData,
+        }
+        .write(&mut world);
+
+        assert_eq!(child_of(&mut world, b), vec!["y"]);
+
+        // Despawning a target removes the pair from its fosters.
+        CheckedDespawn { entity: b }.write(&mut world);
+
+        assert!(!world.entity(y).contains_id(pair_b));
+        assert_eq!(child_of(&mut world, a), vec!["x"]);
+
+        // The component id of `b` is reused by the next target.
+        let c = world.spawn(Name("c")).id();
+        world.set_relations_batch([(y, c, ChildOf)]);
+
+        assert_eq!(world.resource::<Pairs>().id::<ChildOf>(c), Some(pair_b));
+        assert_eq!(child_of(&mut world, c), vec!["y"]);
+    }
+
+    #[test]
+    fn iter_pair() {
+        let mut world = World::new();
+
+        let a = world.spawn(Name("a")).id();
+        let b = world.spawn(Name("b")).id();
+        let x = world.spawn(Name("x")).id();
+        let y = world.spawn((Name("y"), Marker)).id();
+        let z = world.spawn((Name("z"), Marker)).id();
+
+        world.set_relations_batch([
+            (x, a, Recursive),
+            (x, b, Recursive),
+            (y, a, Recursive),
+            (y, b, Recursive),
+            (z, b, Recursive),
+        ]);
+
+        let pairs = world.resource::<Pairs>();
+        let (pair_a, pair_b) = (
+            pairs.id::<Recursive>(a).unwrap(),
+            pairs.id::<Recursive>(b).unwrap(),
+        );
+        assert!(world.entity(x).contains_id(pair_a) && world.entity(x).contains_id(pair_b));
+
+        let mut system_state =
+            SystemState::<(Res<Pairs>, Query<&Name, With<Marker>>)>::new(&mut world);
+        let (pairs, names) = system_state.get(&world);
+
+        let mut found = names
+            .iter_pair::<Recursive>(&pairs, b)
+            .map(|name| name.0)
+            .collect::<Vec<_>>();
+        found.sort();
+        assert_eq!(found, vec!["y", "z"]);
+
+        let found = names
+            .iter_pair::<Recursive>(&pairs, a)
+            .map(|name| name.0)
+            .collect::<Vec<_>>();
+        assert_eq!(found, vec!["y"]);
+
+        assert_eq!(names.iter_pair::<Recursive>(&pairs, x).count(), 0);
+    }
+
+    #[test]
+    fn pair_policies() {
+        let mut world = World::new();
+
+        let root = world.spawn(Name("root")).id();
+        let child = world.spawn(Name("child")).id();
+        let other = world.spawn(Name("other")).id();
+
+        Set {
+            foster: root,
+            target: child,
+            relation: Recursive,
+        }
+        .write(&mut world);
+
+        Set {
+            foster: other,
+            target: child,
+            relation: Recursive,
+        }
+        .write(&mut world);
+
+        let pair = world.resource::<Pairs>().id::<Recursive>(child).unwrap();
+        assert!(world.entity(root).contains_id(pair));
+
+        CheckedDespawn { entity: root }.write(&mut world);
+
+        assert!(world.get_entity(child).is_none());
+        assert!(!world.entity(other).contains_id(pair));
+    }
+}
@@ -0,0 +1,367 @@
+use super::{joins::*, *};
+use crate::{
+    entity::Entity,
+    query::{BatchingStrategy, ReadOnlyWorldQuery, WorldQuery},
+    system::Query,
+};
+
+// Only implemented for joins that are read-only (`&Query`). Each foster is visited by exactly one
+// task and owns its `Storage<R>`, so mutable fosters are fine. Mutable joins are not offered since
+// several fosters may share the same target which would alias across tasks.
+// Impls for different numbers of relations would overlap since they only differ in associated
+// types, so there is one impl per query kind which loops through `EdgePermutations`.
+pub trait ParForEachPermutations {
+    type Components<'c>;
+    type Joins<'i, 'a, 'j>;
+
+    fn par_for_each_batched<Func>(self, batching_strategy: BatchingStrategy, func: Func)
+    where
+        Func: for<'r, 'c, 'i, 'a, 'j> Fn(&'r mut Self::Components<'c>, Self::Joins<'i, 'a, 'j>)
+            + Send
+            + Sync;
+
+    fn par_for_each<Func>(self, func: Func)
+    where
+        Self: Sized,
+        Func: for<'r, 'c, 'i, 'a, 'j> Fn(&'r mut Self::Components<'c>, Self::Joins<'i, 'a, 'j>)
+            + Send
+            + Sync,
+    {
+        self.par_for_each_batched(BatchingStrategy::new(), func);
+    }
+}
+
+/// Nested loops over the edges of a flattened tuple of relations, skipping targets that are
+/// missing from the joins.
+pub trait EdgePermutations {
+    type Entities;
+    type Matches;
+    type Indices;
+
+    fn for_each_permutation<J>(
+        edges: &Edges,
+        joins: &mut J,
+        func: impl FnMut(&mut J, Self::Indices, Self::Entities),
+    ) where
+        J: for<'j> Joinable<'j, Self::Entities, Self::Matches>;
+}
+
+impl<E0: Relation> EdgePermutations for (E0,) {
+    type Entities = (Entity,);
+    type Matches = (bool,);
+    type Indices = (usize,);
+
+    fn for_each_permutation<J>(
+        edges: &Edges,
+        joins: &mut J,
+        mut func: impl FnMut(&mut J, Self::Indices, Self::Entities),
+    ) where
+        J: for<'j> Joinable<'j, Self::Entities, Self::Matches>,
+    {
+        'l0: for (e0, i0) in edges.iter::<E0>() {
+            let (m0,) = joins.contains((e0,));
+            if !m0 {
+                continue 'l0;
+            }
+            func(joins, (i0,), (e0,));
+        }
+    }
+}
+
+impl<E0: Relation, E1: Relation> EdgePermutations for (E0, E1) {
+    type Entities = (Entity, Entity);
+    type Matches = (bool, bool);
+    type Indices = (usize, usize);
+
+    fn for_each_permutation<J>(
+        edges: &Edges,
+        joins: &mut J,
+        mut func: impl FnMut(&mut J, Self::Indices, Self::Entities),
+    ) where
+        J: for<'j> Joinable<'j, Self::Entities, Self::Matches>,
+    {
+        'l0: for (e0, i0) in edges.iter::<E0>() {
+            'l1: for (e1, i1) in edges.iter::<E1>() {
+                let (m0, m1) = joins.contains((e0, e1));
+                if !m0 {
+                    continue 'l0;
+                }
+                if !m1 {
+                    continue 'l1;
+                }
+                func(joins, (i0, i1), (e0, e1));
+            }
+        }
+    }
+}
+
+type EdgesOf<R, EdgeComb> =
+    <<EdgeComb as Comb<<R as RelationQuerySet>::Types>>::Out as Flatten<()>>::Out;
+type EntitiesOf<R, EdgeComb> = <EdgesOf<R, EdgeComb> as EdgePermutations>::Entities;
+type MatchesOf<R, EdgeComb> = <EdgesOf<R, EdgeComb> as EdgePermutations>::Matches;
+type IndicesOf<R, EdgeComb> = <EdgesOf<R, EdgeComb> as EdgePermutations>::Indices;
+
+impl<Q, R, F, Joins, EdgeComb, StorageComb> ParForEachPermutations
+    for Ops<&'_ Query<'_, '_, (Q, Relations<R>), F>, Joins, EdgeComb, StorageComb>
+where
+    Q: 'static + WorldQuery,
+    F: 'static + ReadOnlyWorldQuery,
+    R: RelationQuerySet,
+    EdgeComb: Comb<R::Types>,
+    <EdgeComb as Comb<R::Types>>::Out: Flatten<()>,
+    EdgesOf<R, EdgeComb>: EdgePermutations,
+    Joins: Flatten<()>,
+    <Joins as Flatten<()>>::Out: Clone + Sync,
+    for<'j> <Joins as Flatten<()>>::Out:
+        Joinable<'j, EntitiesOf<R, EdgeComb>, MatchesOf<R, EdgeComb>>,
+    for<'i> StorageComb: Comb<RelationItem<'i, R>>,
+    for<'i> <StorageComb as Comb<RelationItem<'i, R>>>::Out: Flatten<()>,
+    for<'i, 'a, 'j> <<StorageComb as Comb<RelationItem<'i, R>>>::Out as Flatten<()>>::Out: Attach<
+        'a,
+        IndicesOf<R, EdgeComb>,
+        <<Joins as Flatten<()>>::Out as Joinable<
+            'j,
+            EntitiesOf<R, EdgeComb>,
+            MatchesOf<R, EdgeComb>,
+        >>::Out,
+    >,
+{
+    type Components<'c> = <<Q as WorldQuery>::ReadOnly as WorldQuery>::Item<'c>;
+    type Joins<'i, 'a, 'j> =
+        <<<StorageComb as Comb<RelationItem<'i, R>>>::Out as Flatten<()>>::Out as Attach<
+            'a,
+            IndicesOf<R, EdgeComb>,
+            <<Joins as Flatten<()>>::Out as Joinable<
+                'j,
+                EntitiesOf<R, EdgeComb>,
+                MatchesOf<R, EdgeComb>,
+            >>::Out,
+        >>::Out;
+
+    fn par_for_each_batched<Func>(self, batching_strategy: BatchingStrategy, func: Func)
+    where
+        Func: for<'r, 'c, 'i, 'a, 'j> Fn(&'r mut Self::Components<'c>, Self::Joins<'i, 'a, 'j>)
+            + Send
+            + Sync,
+    {
+        let joins = self.joins.flatten(());
+        self.query
+            .par_iter()
+            .batching_strategy(batching_strategy)
+            .for_each(|(mut components, relations)| {
+                let mut joins = joins.clone();
+                let mut storage = StorageComb::comb(relations.world_query).flatten(());
+                EdgesOf::<R, EdgeComb>::for_each_permutation(
+                    relations.edges,
+                    &mut joins,
+                    |joins, indices, entities| {
+                        func(
+                            &mut components,
+                            storage.attach(indices, joins.get(entities)),
+                        );
+                    },
+                );
+            });
+    }
+}
+
+impl<Q, R, F, Joins, EdgeComb, StorageComb> ParForEachPermutations
+    for Ops<&'_ mut Query<'_, '_, (Q, Relations<R>), F>, Joins, EdgeComb, StorageComb>
+where
+    Q: 'static + WorldQuery,
+    F: 'static + ReadOnlyWorldQuery,
+    R: RelationQuerySet,
+    EdgeComb: Comb<R::Types>,
+    <EdgeComb as Comb<R::Types>>::Out: Flatten<()>,
+    EdgesOf<R, EdgeComb>: EdgePermutations,
+    Joins: Flatten<()>,
+    <Joins as Flatten<()>>::Out: Clone + Sync,
+    for<'j> <Joins as Flatten<()>>::Out:
+        Joinable<'j, EntitiesOf<R, EdgeComb>, MatchesOf<R, EdgeComb>>,
+    for<'i> StorageComb: Comb<RelationItemMut<'i, R>>,
+    for<'i> <StorageComb as Comb<RelationItemMut<'i, R>>>::Out: Flatten<()>,
+    for<'i, 'a, 'j> <<StorageComb as Comb<RelationItemMut<'i, R>>>::Out as Flatten<()>>::Out:
+        Attach<
+            'a,
+            IndicesOf<R, EdgeComb>,
+            <<Joins as Flatten<()>>::Out as Joinable<
+                'j,
+                EntitiesOf<R, EdgeComb>,
+                MatchesOf<R, EdgeComb>,
+            >>::Out,
+        >,
+{
+    type Components<'c> = <Q as WorldQuery>::Item<'c>;
+    type Joins<'i, 'a, 'j> =
+        <<<StorageComb as Comb<RelationItemMut<'i, R>>>::Out as Flatten<()>>::Out as Attach<
+            'a,
+            IndicesOf<R, EdgeComb>,
+            <<Joins as Flatten<()>>::Out as Joinable<
+                'j,
+                EntitiesOf<R, EdgeComb>,
+                MatchesOf<R, EdgeComb>,
+            >>::Out,
+        >>::Out;
+
+    fn par_for_each_batched<Func>(self, batching_strategy: BatchingStrategy, func: Func)
+    where
+        Func: for<'r, 'c, 'i, 'a, 'j> Fn(&'r mut Self::Components<'c>, Self::Joins<'i, 'a, 'j>)
+            + Send
+            + Sync,
+    {
+        let joins = self.joins.flatten(());
+        self.query
+            .par_iter_mut()
+            .batching_strategy(batching_strategy)
+            .for_each_mut(|(mut components, relations)| {
+                let mut joins = joins.clone();
+                let mut storage = StorageComb::comb(relations.world_query).flatten(());
+                EdgesOf::<R, EdgeComb>::for_each_permutation(
+                    relations.edges,
+                    &mut joins,
+                    |joins, indices, entities| {
+                        func(
+                            &mut components,
+                            storage.attach(indices, joins.get(entities)),
+                        );
+                    },
+                );
+            });
+    }
+}
+
+#[cfg(test)]
+#[allow(dead_code)]
+#[allow(unused_variables)]
+mod compile_tests {
+    use super::*;
+    use crate::prelude::*;
+
+    #[derive(Component)]
+    struct A;
+
+    #[derive(Relation)]
+    struct B;
+
+    #[derive(Relation)]
+    struct C;
+
+    #[derive(Component)]
+    struct D;
+
+    #[derive(Component)]
+    struct E;
+
+    fn par_join_immut(left: Query<(&A, Relations<(&B, &C)>)>, d: Query<&D>, e: Query<&E>) {
+        left.ops()
+            .join::<B>(&d)
+            .total_join::<C>(&e)
+            .par_for_each(|a, (d, (c, e))| {});
+    }
+
+    fn par_join_left_mut(
+        mut left: Query<(&mut A, Relations<(&mut B, &mut C)>)>,
+        d: Query<&D>,
+        e: Query<&E>,
+    ) {
+        left.ops_mut()
+            .join::<B>(&d)
+            .total_join::<C>(&e)
+            .par_for_each(|a, (d, (c, e))| {});
+    }
+
+    fn par_join_optional(left: Query<(&A, Relations<Option<&B>>)>, d: Query<&D>) {
+        left.ops()
+            .join::<B>(&d)
+            .par_for_each_batched(BatchingStrategy::fixed(16), |a, (d,)| {});
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+    use crate::{self as bevy_ecs, component::TableStorage, prelude::*};
+    use bevy_tasks::{ComputeTaskPool, TaskPool};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn run_system<Param, S: IntoSystem<(), (), Param>>(world: &mut World, system: S) {
+        let mut schedule = Schedule::default();
+        schedule.add_systems(system);
+        schedule.run(world);
+    }
+
+    #[derive(Component)]
+    struct Holder(usize);
+
+    #[derive(Component)]
+    struct Weight(usize);
+
+    struct Carries(usize);
+
+    impl Relation for Carries {
+        type Storage = TableStorage;
+    }
+
+    fn setup(mut commands: Commands) {
+        let items = (1..=10)
+            .map(|weight| commands.spawn(Weight(weight)).id())
+            .collect::<Vec<_>>();
+
+        for n in 0..100 {
+            let holder = commands.spawn(Holder(0)).id();
+            for item in items.iter().take(n % 10 + 1) {
+                commands.add(Set {
+                    foster: holder,
+                    target: *item,
+                    relation: Carries(n),
+                });
+            }
+        }
+    }
+
+    fn total_weight(holders: Query<(&Holder, Relations<&Carries>)>, items: Query<&Weight>) {
+        let total = AtomicUsize::new(0);
+
+        holders.ops().join::<Carries>(&items).par_for_each_batched(
+            BatchingStrategy::fixed(7),
+            |_, (weight,)| {
+                total.fetch_add(weight.0, Ordering::Relaxed);
+            },
+        );
+
+        // Holder `n` carries the first `n % 10 + 1` items, 10 holders per count.
+        let expected = (1..=10).map(|n| 10 * n * (n + 1) / 2).sum::<usize>();
+        assert_eq!(total.into_inner(), expected);
+    }
+
+    fn accumulate(
+        mut holders: Query<(&mut Holder, Relations<&mut Carries>)>,
+        items: Query<&Weight>,
+    ) {
+        holders
+            .ops_mut()
+            .total_join::<Carries>(&items)
+            .par_for_each(|holder, ((carries, weight),)| {
+                holder.0 += weight.0;
+                carries.0 = weight.0;
+            });
+
+        holders
+            .ops_mut()
+            .total_join::<Carries>(&items)
+            .for_each(|_, ((carries, weight),)| assert_eq!(carries.0, weight.0));
+
+        for (holder, _) in holders.iter() {
+            assert!(holder.0 > 0);
+        }
+    }
+
+    #[test]
+    fn par_join_test() {
+        ComputeTaskPool::init(TaskPool::default);
+        let mut world = World::new();
+        run_system(&mut world, setup);
+        run_system(&mut world, total_weight);
+        run_system(&mut world, accumulate);
+    }
+}
@@ -0,0 +1,254 @@
+use super::{iter::*, joins::*, *};
+use crate::{
+    entity::Entity,
+    query::{ROQueryItem, ReadOnlyWorldQuery, WorldQuery},
+    system::Query,
+};
+
+// Read-only `Ops` can be joined like a query. This chains hops across relations:
+// `owners.ops().join::<Owns>(items.ops().join::<LocatedIn>(&rooms))`
+// Matching is an inner join so an entity only matches if it has at least one permutation of
+// the next hop. Each match yields an iterator over the permutations of the next hop.
+#[rustfmt::skip]
+pub type Hops<'o, Q, R, Joins, EdgeComb, StorageComb> = OpsIter<
+    'o,
+    Q,
+    R,
+    Joins,
+    StorageComb,
+    <<EdgeComb as Comb<<R as RelationQuerySet>::Types>>::Out as Flatten<()>>::Out,
+    std::option::IntoIter<ROQueryItem<'o, (Q, Relations<R>)>>,
+>;
+
+// Read-only `Ops` are cheap to copy which lets them be reused for every hop.
+impl<Q, R, F, Joins, EdgeComb, StorageComb> Clone
+    for Ops<&'_ Query<'_, '_, (Q, Relations<R>), F>, Joins, EdgeComb, StorageComb>
+where
+    Q: 'static + WorldQuery,
+    F: 'static + ReadOnlyWorldQuery,
+    R: RelationQuerySet,
+    Joins: Clone,
+{
+    fn clone(&self) -> Self {
+        Ops {
+            query: self.query,
+            joins: self.joins.clone(),
+            edge_comb: PhantomData,
+            storage_comb: PhantomData,
+            traversal: (),
+        }
+    }
+}
+
+impl<'o, Q, R, F, Joins, EdgeComb, StorageComb>
+    Ops<&'o Query<'_, '_, (Q, Relations<R>), F>, Joins, EdgeComb, StorageComb>
+where
+    Q: 'static + WorldQuery,
+    F: 'static + ReadOnlyWorldQuery,
+    R: 'static + RelationQuerySet,
+    EdgeComb: Comb<R::Types>,
+    <EdgeComb as Comb<R::Types>>::Out: Flatten<()>,
+    Joins: Clone + Flatten<()>,
+    StorageComb: Comb<RelationItem<'o, R>>,
+    <StorageComb as Comb<RelationItem<'o, R>>>::Out: Flatten<()>,
+{
+    fn hops(&self, entity: Entity) -> Hops<'o, Q, R, Joins, EdgeComb, StorageComb> {
+        let query: &'o Query<'_, '_, (Q, Relations<R>), F> = self.query;
+        OpsIter::new(query.get(entity).ok().into_iter(), self.joins.clone())
+    }
+}
+
+impl<'a, 'o, Q, R, F, Joins, EdgeComb, StorageComb> Joinable<'a, Entity, bool>
+    for Ops<&'o Query<'_, '_, (Q, Relations<R>), F>, Joins, EdgeComb, StorageComb>
+where
+    Q: 'static + WorldQuery,
+    F: 'static + ReadOnlyWorldQuery,
+    R: 'static + RelationQuerySet,
+    EdgeComb: Comb<R::Types>,
+    <EdgeComb as Comb<R::Types>>::Out: Flatten<()>,
+    Joins: Clone + Flatten<()>,
+    StorageComb: Comb<RelationItem<'o, R>>,
+    <StorageComb as Comb<RelationItem<'o, R>>>::Out: Flatten<()>,
+    Hops<'o, Q, R, Joins, EdgeComb, StorageComb>: Iterator,
+{
+    type Out = Hops<'o, Q, R, Joins, EdgeComb, StorageComb>;
+
+    fn contains(&self, entity: Entity) -> bool {
+        self.hops(entity).next().is_some()
+    }
+
+    fn get(&'a mut self, entity: Entity) -> Self::Out {
+        self.hops(entity)
+    }
+}
+
+impl<'p, 'o, Q, R, F, Joins, EdgeComb, StorageComb> JoinableRef<'p, Entity, bool>
+    for Ops<&'o Query<'_, '_, (Q, Relations<R>), F>, Joins, EdgeComb, StorageComb>
+where
+    Q: 'static + WorldQuery,
+    F: 'static + ReadOnlyWorldQuery,
+    R: 'static + RelationQuerySet,
+    EdgeComb: Comb<R::Types>,
+    <EdgeComb as Comb<R::Types>>::Out: Flatten<()>,
+    Joins: Clone + Flatten<()>,
+    StorageComb: Comb<RelationItem<'o, R>>,
+    <StorageComb as Comb<RelationItem<'o, R>>>::Out: Flatten<()>,
+    Hops<'o, Q, R, Joins, EdgeComb, StorageComb>: Iterator,
+{
+    type Out = Hops<'o, Q, R, Joins, EdgeComb, StorageComb>;
+
+    fn contains(&self, entity: Entity) -> bool {
+        self.hops(entity).next().is_some()
+    }
+
+    fn get_ref(&self, entity: Entity) -> Self::Out {
+        self.hops(entity)
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+    use crate::{self as bevy_ecs, component::TableStorage, prelude::*};
+
+    fn run_system<Param, S: IntoSystem<(), (), Param>>(world: &mut World, system: S) {
+        let mut schedule = Schedule::default();
+        schedule.add_systems(system);
+        schedule.run(world);
+    }
+
+    #[derive(Component)]
+    struct Person(&'static str);
+
+    #[derive(Component)]
+    struct Thing(&'static str);
+
+    #[derive(Component)]
+    struct Room(&'static str);
+
+    #[derive(Component)]
+    struct Floor(u32);
+
+    struct Owns(u32);
+
+    impl Relation for Owns {
+        type Storage = TableStorage;
+    }
+
+    struct LocatedIn(&'static str);
+
+    impl Relation for LocatedIn {
+        type Storage = TableStorage;
+        const EXCLUSIVE: bool = true;
+    }
+
+    struct On;
+
+    impl Relation for On {
+        type Storage = TableStorage;
+    }
+
+    fn setup(mut commands: Commands) {
+        let ground = commands.spawn(Floor(0)).id();
+        let first = commands.spawn(Floor(1)).id();
+
+        let kitchen = commands.spawn(Room("kitchen")).id();
+        let attic = commands.spawn(Room("attic")).id();
+        commands.add(Set {
+            foster: kitchen,
+            target: ground,
+            relation: On,
+        });
+        commands.add(Set {
+            foster: attic,
+            target: first,
+            relation: On,
+        });
+
+        let knife = commands.spawn(Thing("knife")).id();
+        let trunk = commands.spawn(Thing("trunk")).id();
+        let kite = commands.spawn(Thing("kite")).id();
+        commands.add(Set {
+            foster: knife,
+            target: kitchen,
+            relation: LocatedIn("drawer"),
+        });
+        commands.add(Set {
+            foster: trunk,
+            target: attic,
+            relation: LocatedIn("corner"),
+        });
+
+        let ada = commands.spawn(Person("ada")).id();
+        let bo = commands.spawn(Person("bo")).id();
+        for (foster, target, count) in [(ada, knife, 2), (ada, trunk, 1), (bo, kite, 1)] {
+            commands.add(Set {
+                foster,
+                target,
+                relation: Owns(count),
+            });
+        }
+    }
+
+    fn two_hops(
+        people: Query<(&Person, Relations<&Owns>)>,
+        things: Query<(&Thing, Relations<&LocatedIn>)>,
+        rooms: Query<&Room>,
+    ) {
+        let mut found = vec![];
+
+        people
+            .ops()
+            .total_join::<Owns>(things.ops().total_join::<LocatedIn>(&rooms))
+            .for_each(|person, ((owns, hops),)| {
+                for (thing, ((located_in, room),)) in hops {
+                    found.push((person.0, owns.0, thing.0, located_in.0, room.0));
+                }
+            });
+
+        found.sort();
+        assert_eq!(
+            found,
+            vec![
+                ("ada", 1, "trunk", "corner", "attic"),
+                ("ada", 2, "knife", "drawer", "kitchen"),
+            ]
+        );
+    }
+
+    fn three_hops(
+        people: Query<(&Person, Relations<&Owns>)>,
+        things: Query<(&Thing, Relations<&LocatedIn>)>,
+        rooms: Query<(&Room, Relations<&On>)>,
+        floors: Query<&Floor>,
+    ) {
+        let mut found = people
+            .ops()
+            .join::<Owns>(
+                things
+                    .ops()
+                    .join::<LocatedIn>(rooms.ops().join::<On>(&floors)),
+            )
+            .into_iter()
+            .flat_map(|(person, (things,))| {
+                things.flat_map(move |(thing, (rooms,))| {
+                    rooms.map(move |(room, (floor,))| (person.0, thing.0, room.0, floor.0))
+                })
+            })
+            .collect::<Vec<_>>();
+
+        found.sort();
+        assert_eq!(
+            found,
+            vec![("ada", "knife", "kitchen", 0), ("ada", "trunk", "attic", 1)]
+        );
+    }
+
+    #[test]
+    fn path_test() {
+        let mut world = World::new();
+        run_system(&mut world, setup);
+        run_system(&mut world, two_hops);
+        run_system(&mut world, three_hops);
+    }
+}
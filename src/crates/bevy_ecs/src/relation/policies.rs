@@ -4,7 +4,7 @@ use bevy_utils::{HashMap, HashSet};
 
 use crate::{entity::Entity, world::World};
 
-use super::Edges;
+use super::{closure::*, hooks::*, islands::*, pairs::*, Edges};
 
 // Precedence: Most data latering operation is preferred.
 // Smaller number -> Higher precedence
@@ -27,8 +27,12 @@ impl Operation {
         match self {
             Operation::Despawn(entity) => {
                 world.despawn(*entity);
+                closure_despawn(world, *entity);
+                islands_despawn(world, *entity);
             }
             Operation::Delink(parent, relation, child) => {
+                let mut removed = false;
+
                 if let Some(mut parent_mut) = world.get_entity_mut(*parent) {
                     let mut edges = parent_mut
                         .get_mut::<Edges>()
@@ -36,7 +40,7 @@ impl Operation {
                     let Some(policy) = DespawnPolicy::iterator().find(| &policy | edges.targets[*policy as usize].contains_key(relation)) else { return };
 
                     if let Some(targets) = edges.targets[*policy as usize].get_mut(relation) {
-                        targets.remove(child);
+                        removed = targets.shift_remove(child).is_some();
                     }
                 }
 
@@ -49,6 +53,14 @@ impl Operation {
                         fosters.remove(parent);
                     }
                 }
+
+                remove_pair(world, *parent, *relation, *child);
+                closure_remove(world, *relation, *parent);
+                islands_remove(world, *relation, *parent);
+
+                if removed {
+                    hook_remove(world, *relation, *parent, *child);
+                }
             }
             Operation::Reparent(child, relation) => {
                 let Some(mut child_mut) = world.get_entity_mut(*child) else { return };
@@ -76,6 +88,11 @@ impl Operation {
                             .entry(*relation)
                             .or_default()
                             .insert(*child, storage_index);
+
+                        reinsert_pair(world, parent, *relation, *child);
+                        closure_insert(world, *relation, parent, *child);
+                        islands_insert(world, *relation, parent, *child);
+                        hook_set(world, *relation, parent, *child);
                     }
                 }
             }
@@ -254,6 +271,49 @@ fn apply_operations(
     for operation in operations.iter() {
         operation.apply(world, ascended_parents);
     }
+
+    // Pairs are freed once every foster has been delinked from the despawned targets.
+    for operation in operations.iter() {
+        if let Operation::Despawn(entity) = operation {
+            pairs_despawn(world, *entity);
+        }
+    }
+}
+
+// Despawns `entity` and removes every edge it takes part in without applying any despawn policy.
+pub(crate) fn orphan_despawn(world: &mut World, entity: Entity) {
+    let Some(edges) = world.get::<Edges>(entity) else {
+        world.despawn(entity);
+        return;
+    };
+
+    let mut operations = edges
+        .fosters
+        .iter()
+        .flat_map(|(relation, fosters)| {
+            fosters
+                .iter()
+                .map(move |foster| Operation::Delink(*foster, *relation, entity))
+        })
+        .collect::<Vec<_>>();
+
+    operations.extend(
+        edges
+            .targets
+            .iter()
+            .flatten()
+            .flat_map(|(relation, targets)| {
+                targets
+                    .keys()
+                    .map(move |target| Operation::Delink(entity, *relation, *target))
+            }),
+    );
+    operations.push(Operation::Despawn(entity));
+
+    let ascended_parents = AscendedParents {
+        parents: HashMap::new(),
+    };
+    apply_operations(world, &operations, &ascended_parents);
 }
 
 impl DespawnPolicy {
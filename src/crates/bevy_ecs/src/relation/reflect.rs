@@ -0,0 +1,116 @@
+use super::*;
+use crate::world::FromWorld;
+use bevy_reflect::{FromType, Reflect};
+
+// Type erased access to the edges of a relation so scenes and other reflection based tools can
+// save and recreate them without knowing the relation type.
+
+/// A struct used to operate on the edges of a reflected [`Relation`] type.
+///
+/// A [`ReflectRelation`] for type `T` can be obtained via
+/// [`bevy_reflect::TypeRegistration::data`] after registering `T` with `#[reflect(Relation)]`.
+#[derive(Clone)]
+pub struct ReflectRelation {
+    despawn_policy: DespawnPolicy,
+    exclusive: bool,
+    set: fn(&mut World, Entity, Entity, &dyn Reflect),
+    targets: fn(&World, Entity) -> Vec<(Entity, &dyn Reflect)>,
+}
+
+impl ReflectRelation {
+    /// The [`Relation::DESPAWN_POLICY`] of the relation.
+    pub fn despawn_policy(&self) -> DespawnPolicy {
+        self.despawn_policy
+    }
+
+    /// The [`Relation::EXCLUSIVE`] flag of the relation.
+    pub fn exclusive(&self) -> bool {
+        self.exclusive
+    }
+
+    /// Sets an edge from `foster` to `target` like [`Set`] with a value built from `relation`.
+    pub fn set(&self, world: &mut World, foster: Entity, target: Entity, relation: &dyn Reflect) {
+        (self.set)(world, foster, target, relation);
+    }
+
+    /// Targets of `foster` in order together with the value of each edge.
+    pub fn targets<'w>(&self, world: &'w World, foster: Entity) -> Vec<(Entity, &'w dyn Reflect)> {
+        (self.targets)(world, foster)
+    }
+}
+
+impl<R: Relation + Reflect + FromWorld> FromType<R> for ReflectRelation {
+    fn from_type() -> Self {
+        ReflectRelation {
+            despawn_policy: R::DESPAWN_POLICY,
+            exclusive: R::EXCLUSIVE,
+            set: |world, foster, target, reflected_relation| {
+                let mut relation = R::from_world(world);
+                relation.apply(reflected_relation);
+                Set {
+                    foster,
+                    target,
+                    relation,
+                }
+                .write(world);
+            },
+            targets: |world, foster| {
+                let Some(foster_ref) = world.get_entity(foster) else {
+                    return Vec::new();
+                };
+
+                let (Some(edges), Some(storage)) =
+                    (foster_ref.get::<Edges>(), foster_ref.get::<Storage<R>>())
+                else {
+                    return Vec::new();
+                };
+
+                edges
+                    .iter::<R>()
+                    .filter_map(|(target, index)| {
+                        Some((target, storage.values.get(index)? as &dyn Reflect))
+                    })
+                    .collect()
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+    use crate::component::TableStorage;
+    use bevy_reflect::{FromReflect, GetTypeRegistration};
+
+    #[derive(Reflect, FromReflect, Default, Debug, PartialEq)]
+    #[reflect(Relation)]
+    struct MountedOn(u32);
+
+    impl Relation for MountedOn {
+        type Storage = TableStorage;
+    }
+
+    #[test]
+    fn reflect_relation() {
+        let mut world = World::new();
+        let [a, b, c] = [(); 3].map(|_| world.spawn_empty().id());
+        world.set_relations_batch([(a, b, MountedOn(1)), (a, c, MountedOn(2))]);
+
+        let registration = MountedOn::get_type_registration();
+        let reflect_relation = registration.data::<ReflectRelation>().unwrap();
+
+        let targets = reflect_relation
+            .targets(&world, a)
+            .into_iter()
+            .map(|(target, relation)| (target, MountedOn::from_reflect(relation).unwrap()))
+            .collect::<Vec<_>>();
+        assert_eq!(targets, vec![(b, MountedOn(1)), (c, MountedOn(2))]);
+
+        reflect_relation.set(&mut world, c, b, &MountedOn(3));
+        assert_eq!(
+            reflect_relation.targets(&world, c)[0].1.downcast_ref(),
+            Some(&MountedOn(3))
+        );
+        assert!(reflect_relation.targets(&world, b).is_empty());
+    }
+}
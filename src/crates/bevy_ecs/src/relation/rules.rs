@@ -0,0 +1,419 @@
+use super::*;
+use crate::world::EntityRef;
+
+// Datalog style rules over relations and components.
+// `Enemy(X, Y) :- Faction(X, F1), Faction(Y, F2), Hostile(F1, F2)` is written as:
+// ```
+// Rules::new().rule("enemy", ["X", "Y"], [
+//     relation::<Faction>("X", "F1"),
+//     relation::<Faction>("Y", "F2"),
+//     relation::<Hostile>("F1", "F2"),
+// ])
+// ```
+// Relation and component atoms are read from the world once per evaluation. Derived atoms refer
+// to the head of other rules, including recursively, and are evaluated semi-naively: every round
+// only joins against the facts that were new in the previous round.
+// TODO: Negation, body atoms are joined in order without any planning.
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Term {
+    Var(&'static str),
+    Const(Entity),
+}
+
+impl From<&'static str> for Term {
+    fn from(var: &'static str) -> Self {
+        Term::Var(var)
+    }
+}
+
+impl From<Entity> for Term {
+    fn from(entity: Entity) -> Self {
+        Term::Const(entity)
+    }
+}
+
+type Predicate = Box<dyn Fn(&EntityRef) -> bool + Send + Sync>;
+
+enum Source {
+    Relation(TypeId),
+    Component(Predicate),
+    Derived(&'static str),
+}
+
+pub struct Atom {
+    source: Source,
+    terms: Vec<Term>,
+}
+
+/// `R(foster, target)`
+pub fn relation<R: Relation>(foster: impl Into<Term>, target: impl Into<Term>) -> Atom {
+    Atom {
+        source: Source::Relation(TypeId::of::<Storage<R>>()),
+        terms: vec![foster.into(), target.into()],
+    }
+}
+
+/// Entities with component `C`.
+pub fn has<C: Component>(term: impl Into<Term>) -> Atom {
+    Atom {
+        source: Source::Component(Box::new(|entity| entity.contains::<C>())),
+        terms: vec![term.into()],
+    }
+}
+
+/// Entities with component `C` for which `predicate` holds.
+pub fn satisfies<C: Component>(
+    term: impl Into<Term>,
+    predicate: impl 'static + Fn(&C) -> bool + Send + Sync,
+) -> Atom {
+    Atom {
+        source: Source::Component(Box::new(move |entity| {
+            entity.get::<C>().map_or(false, &predicate)
+        })),
+        terms: vec![term.into()],
+    }
+}
+
+/// Facts derived by the rules with head `name`.
+pub fn derived<T: Into<Term>>(name: &'static str, terms: impl IntoIterator<Item = T>) -> Atom {
+    Atom {
+        source: Source::Derived(name),
+        terms: terms.into_iter().map(Into::into).collect(),
+    }
+}
+
+struct Rule {
+    head: &'static str,
+    terms: Vec<Term>,
+    body: Vec<Atom>,
+}
+
+#[derive(Default)]
+pub struct Rules {
+    rules: Vec<Rule>,
+}
+
+type Table = HashSet<Vec<Entity>>;
+type Bindings = HashMap<&'static str, Entity>;
+
+impl Rules {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// # Panics
+    ///
+    /// Panics if a variable of the head does not appear in the body.
+    pub fn rule<T: Into<Term>>(
+        mut self,
+        head: &'static str,
+        terms: impl IntoIterator<Item = T>,
+        body: impl IntoIterator<Item = Atom>,
+    ) -> Self {
+        let terms = terms.into_iter().map(Into::into).collect::<Vec<_>>();
+        let body = body.into_iter().collect::<Vec<_>>();
+
+        for term in terms.iter() {
+            if let Term::Var(var) = term {
+                assert!(
+                    body.iter().any(|atom| atom.terms.contains(term)),
+                    "Head variable {var} of rule {head} must appear in its body"
+                );
+            }
+        }
+
+        self.rules.push(Rule { head, terms, body });
+        self
+    }
+
+    pub fn evaluate(&self, world: &World) -> Facts {
+        let base = self.base_tables(world);
+        let empty = Table::default();
+
+        let mut full = HashMap::<&'static str, Table>::new();
+        let mut delta = HashMap::<&'static str, Table>::new();
+
+        for (rule, base) in self.rules.iter().zip(base.iter()) {
+            let facts = derive(rule, |index| match &rule.body[index].source {
+                Source::Derived(_) => &empty,
+                _ => &base[index],
+            });
+            delta.entry(rule.head).or_default().extend(facts);
+        }
+
+        while delta.values().any(|facts| !facts.is_empty()) {
+            for (head, facts) in delta.iter() {
+                full.entry(head).or_default().extend(facts.iter().cloned());
+            }
+
+            let mut next = HashMap::<&'static str, Table>::new();
+
+            for (rule, base) in self.rules.iter().zip(base.iter()) {
+                for (changed, _) in rule
+                    .body
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, atom)| matches!(atom.source, Source::Derived(_)))
+                {
+                    let facts = derive(rule, |index| match &rule.body[index].source {
+                        Source::Derived(name) if index == changed => {
+                            delta.get(name).unwrap_or(&empty)
+                        }
+                        Source::Derived(name) => full.get(name).unwrap_or(&empty),
+                        _ => &base[index],
+                    });
+
+                    let known = full.get(rule.head);
+                    next.entry(rule.head).or_default().extend(
+                        facts
+                            .into_iter()
+                            .filter(|fact| known.map_or(true, |known| !known.contains(fact))),
+                    );
+                }
+            }
+
+            delta = next;
+        }
+
+        Facts { facts: full }
+    }
+
+    // Reads every relation and component atom from the world. Derived atoms get an empty table.
+    fn base_tables(&self, world: &World) -> Vec<Vec<Table>> {
+        let mut relations = HashMap::<TypeId, Table>::new();
+
+        self.rules
+            .iter()
+            .map(|rule| {
+                rule.body
+                    .iter()
+                    .map(|atom| match &atom.source {
+                        Source::Relation(relation) => relations
+                            .entry(*relation)
+                            .or_insert_with(|| relation_table(world, *relation))
+                            .clone(),
+                        Source::Component(predicate) => world
+                            .iter_entities()
+                            .filter(|entity| predicate(entity))
+                            .map(|entity| vec![entity.id()])
+                            .collect(),
+                        Source::Derived(_) => Table::default(),
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+fn relation_table(world: &World, relation: TypeId) -> Table {
+    let mut table = Table::default();
+
+    for entity in world.iter_entities() {
+        let Some(edges) = entity.get::<Edges>() else {
+            continue;
+        };
+
+        for targets in edges
+            .targets
+            .iter()
+            .filter_map(|targets| targets.get(&relation))
+        {
+            table.extend(targets.keys().map(|target| vec![entity.id(), *target]));
+        }
+    }
+
+    table
+}
+
+fn unify(terms: &[Term], fact: &[Entity], bindings: &Bindings) -> Option<Bindings> {
+    if terms.len() != fact.len() {
+        return None;
+    }
+
+    let mut bindings = bindings.clone();
+
+    for (term, entity) in terms.iter().zip(fact.iter()) {
+        match term {
+            Term::Const(constant) if constant != entity => return None,
+            Term::Const(_) => (),
+            Term::Var(var) => {
+                if *bindings.entry(var).or_insert(*entity) != *entity {
+                    return None;
+                }
+            }
+        }
+    }
+
+    Some(bindings)
+}
+
+fn derive<'t>(rule: &Rule, table: impl Fn(usize) -> &'t Table) -> Vec<Vec<Entity>> {
+    let mut bindings = vec![Bindings::new()];
+
+    for (index, atom) in rule.body.iter().enumerate() {
+        let facts = table(index);
+
+        bindings = bindings
+            .iter()
+            .flat_map(|bindings| {
+                facts
+                    .iter()
+                    .filter_map(|fact| unify(&atom.terms, fact, bindings))
+            })
+            .collect();
+
+        if bindings.is_empty() {
+            return Vec::new();
+        }
+    }
+
+    bindings
+        .iter()
+        .map(|bindings| {
+            rule.terms
+                .iter()
+                .map(|term| match term {
+                    Term::Const(entity) => *entity,
+                    Term::Var(var) => bindings[var],
+                })
+                .collect()
+        })
+        .collect()
+}
+
+pub struct Facts {
+    facts: HashMap<&'static str, Table>,
+}
+
+impl Facts {
+    pub fn get(&self, name: &str) -> impl '_ + Iterator<Item = &[Entity]> {
+        self.facts
+            .get(name)
+            .into_iter()
+            .flatten()
+            .map(Vec::as_slice)
+    }
+
+    pub fn contains(&self, name: &str, fact: &[Entity]) -> bool {
+        self.facts
+            .get(name)
+            .map_or(false, |facts| facts.contains(fact))
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+    use crate::{self as bevy_ecs, component::TableStorage};
+
+    #[derive(Component)]
+    struct Level(u32);
+
+    struct Faction;
+
+    impl Relation for Faction {
+        type Storage = TableStorage;
+        const EXCLUSIVE: bool = true;
+    }
+
+    struct Hostile;
+
+    impl Relation for Hostile {
+        type Storage = TableStorage;
+    }
+
+    struct Link;
+
+    impl Relation for Link {
+        type Storage = TableStorage;
+    }
+
+    fn sorted<'a>(facts: impl Iterator<Item = &'a [Entity]>) -> Vec<Vec<Entity>> {
+        let mut facts = facts.map(<[Entity]>::to_vec).collect::<Vec<_>>();
+        facts.sort();
+        facts
+    }
+
+    #[test]
+    fn join_rule() {
+        let mut world = World::new();
+        let [red, blue, green] = [(); 3].map(|_| world.spawn_empty().id());
+        let a = world.spawn(Level(1)).id();
+        let b = world.spawn(Level(3)).id();
+        let c = world.spawn(Level(5)).id();
+
+        world.set_relations_batch([(a, red, Faction), (b, blue, Faction), (c, green, Faction)]);
+        world.set_relations_batch([(red, blue, Hostile), (green, blue, Hostile)]);
+
+        let facts = Rules::new()
+            .rule(
+                "enemy",
+                ["X", "Y"],
+                [
+                    relation::<Faction>("X", "F1"),
+                    relation::<Faction>("Y", "F2"),
+                    relation::<Hostile>("F1", "F2"),
+                ],
+            )
+            .rule(
+                "threat",
+                ["X"],
+                [
+                    derived("enemy", ["X", "Y"]),
+                    satisfies::<Level>("X", |level| level.0 > 2),
+                ],
+            )
+            .rule(
+                "enemy_of_blue",
+                ["X"],
+                [
+                    relation::<Faction>("X", "F"),
+                    relation::<Hostile>("F", Term::Const(blue)),
+                    has::<Level>("X"),
+                ],
+            )
+            .evaluate(&world);
+
+        assert_eq!(sorted(facts.get("enemy")), vec![vec![a, b], vec![c, b]]);
+        assert_eq!(sorted(facts.get("threat")), vec![vec![c]]);
+        assert_eq!(sorted(facts.get("enemy_of_blue")), vec![vec![a], vec![c]]);
+        assert!(!facts.contains("enemy", &[b, a]));
+        assert_eq!(facts.get("unknown").count(), 0);
+    }
+
+    #[test]
+    fn recursive_rule() {
+        let mut world = World::new();
+        let nodes = [(); 6].map(|_| world.spawn_empty().id());
+        let [n0, n1, n2, n3, n4, n5] = nodes;
+
+        world.set_relations_batch([
+            (n0, n1, Link),
+            (n1, n2, Link),
+            (n2, n0, Link),
+            (n2, n3, Link),
+            (n4, n5, Link),
+        ]);
+
+        let facts = Rules::new()
+            .rule("reaches", ["X", "Y"], [relation::<Link>("X", "Y")])
+            .rule(
+                "reaches",
+                ["X", "Y"],
+                [derived("reaches", ["X", "Z"]), relation::<Link>("Z", "Y")],
+            )
+            .evaluate(&world);
+
+        for from in nodes {
+            for to in nodes {
+                assert_eq!(
+                    facts.contains("reaches", &[from, to]),
+                    world.is_reachable::<Link>(from, to)
+                );
+            }
+        }
+
+        assert_eq!(facts.get("reaches").count(), 3 * 4 + 1);
+    }
+}
@@ -0,0 +1,210 @@
+use super::*;
+use bevy_utils::FloatOrd;
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+// Shortest paths along the edges of a relation. Only entities matched by the query are traversed
+// so filters decide which nodes are walkable. The relation is optional so that sinks, which only
+// have `Edges` as targets, are matched and filtered like every other node.
+// Costs come from the relation values and must not be negative.
+impl<'w, 's, Q, F, R> Query<'w, 's, (Q, Relations<Option<&'static R>>), F>
+where
+    Q: 'static + WorldQuery,
+    F: 'static + ReadOnlyWorldQuery,
+    R: Relation,
+{
+    /// Dijkstra search from `start` to `goal`. Returns the entities along the path, including
+    /// both ends, and its total cost.
+    pub fn shortest_path(
+        &self,
+        start: Entity,
+        goal: Entity,
+        cost: impl Fn(&R) -> f32,
+    ) -> Option<(Vec<Entity>, f32)> {
+        self.shortest_path_with_heuristic(start, goal, cost, |_| 0.)
+    }
+
+    /// A* search from `start` to `goal`. `heuristic` estimates the remaining cost to `goal`.
+    ///
+    /// Nodes are never revisited, so for the returned path to be the shortest the heuristic
+    /// must be consistent: for every edge from `a` to `b`, `heuristic(a)` may not exceed the
+    /// cost of the edge plus `heuristic(b)`, and `heuristic(goal)` must be `0`. Distances such
+    /// as the straight line to the goal are consistent when costs are at least as long.
+    pub fn shortest_path_with_heuristic(
+        &self,
+        start: Entity,
+        goal: Entity,
+        cost: impl Fn(&R) -> f32,
+        heuristic: impl Fn(Entity) -> f32,
+    ) -> Option<(Vec<Entity>, f32)> {
+        if !self.contains(start) {
+            return None;
+        }
+
+        let mut best = HashMap::<Entity, (f32, Option<Entity>)>::from_iter([(start, (0., None))]);
+        let mut open = BinaryHeap::from([(Reverse(FloatOrd(heuristic(start))), start)]);
+        let mut closed = HashSet::<Entity>::new();
+
+        while let Some((_, entity)) = open.pop() {
+            if !closed.insert(entity) {
+                continue;
+            }
+
+            let distance = best[&entity].0;
+
+            if entity == goal {
+                let mut path = vec![goal];
+                while let Some((_, Some(previous))) = best.get(path.last().unwrap()) {
+                    path.push(*previous);
+                }
+                path.reverse();
+                return Some((path, distance));
+            }
+
+            let Ok((_, relations)) = self.get(entity) else {
+                continue;
+            };
+            let Some(storage) = relations.world_query else {
+                continue;
+            };
+
+            for (target, index) in relations.edges.targets_of::<R>() {
+                if closed.contains(target) || !self.contains(*target) {
+                    continue;
+                }
+
+                let distance = distance + cost(&storage.storage.values[*index]);
+
+                if best
+                    .get(target)
+                    .map_or(true, |(known, _)| distance < *known)
+                {
+                    best.insert(*target, (distance, Some(entity)));
+                    open.push((Reverse(FloatOrd(distance + heuristic(*target))), *target));
+                }
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+    use crate::{self as bevy_ecs, component::TableStorage, prelude::*, system::SystemState};
+
+    #[derive(Component)]
+    struct Position(f32, f32);
+
+    #[derive(Component)]
+    struct Blocked;
+
+    struct Road(f32);
+
+    impl Relation for Road {
+        type Storage = TableStorage;
+    }
+
+    type Graph<'w, 's, F = ()> = Query<'w, 's, (Entity, Relations<Option<&'static Road>>), F>;
+
+    #[test]
+    fn shortest_paths() {
+        let mut world = World::new();
+
+        // a - b - d is cheaper than a - c - d unless b is blocked.
+        let a = world.spawn(Position(0., 0.)).id();
+        let b = world.spawn(Position(1., 1.)).id();
+        let c = world.spawn(Position(1., -1.)).id();
+        let d = world.spawn(Position(2., 0.)).id();
+        let e = world.spawn(Position(9., 9.)).id();
+
+        // `f` has no roads of its own.
+        let f = world.spawn(Position(3., 0.)).id();
+
+        world.set_relations_batch([
+            (a, b, Road(1.)),
+            (b, d, Road(1.)),
+            (a, c, Road(2.)),
+            (c, d, Road(2.)),
+            (d, a, Road(1.)),
+            (d, f, Road(1.)),
+        ]);
+
+        let mut system_state = SystemState::<(Graph, Query<&Position>)>::new(&mut world);
+        let (graph, positions) = system_state.get_mut(&mut world);
+
+        assert_eq!(
+            graph.shortest_path(a, d, |road| road.0),
+            Some((vec![a, b, d], 2.))
+        );
+        assert_eq!(
+            graph.shortest_path(a, a, |road| road.0),
+            Some((vec![a], 0.))
+        );
+        assert_eq!(graph.shortest_path(a, e, |road| road.0), None);
+        assert_eq!(
+            graph.shortest_path(a, f, |road| road.0),
+            Some((vec![a, b, d, f], 3.))
+        );
+
+        let goal = positions.get(d).unwrap();
+        let distance = |entity| {
+            let position = positions.get(entity).unwrap();
+            ((position.0 - goal.0).powi(2) + (position.1 - goal.1).powi(2)).sqrt()
+        };
+
+        assert_eq!(
+            graph.shortest_path_with_heuristic(a, d, |road| road.0, distance),
+            Some((vec![a, b, d], 2.))
+        );
+
+        // Hop count instead of road length.
+        assert_eq!(graph.shortest_path(d, b, |_| 1.), Some((vec![d, a, b], 2.)));
+
+        world.entity_mut(b).insert(Blocked);
+
+        let mut system_state = SystemState::<Graph<Without<Blocked>>>::new(&mut world);
+        let graph = system_state.get_mut(&mut world);
+
+        assert_eq!(
+            graph.shortest_path(a, d, |road| road.0),
+            Some((vec![a, c, d], 4.))
+        );
+        assert_eq!(graph.shortest_path(a, b, |road| road.0), None);
+
+        // Sinks are still filtered.
+        world.entity_mut(f).insert(Blocked);
+
+        let mut system_state = SystemState::<Graph<Without<Blocked>>>::new(&mut world);
+        let graph = system_state.get_mut(&mut world);
+
+        assert_eq!(graph.shortest_path(a, f, |road| road.0), None);
+
+        // Non-archetypal filters apply to sinks too.
+        world.entity_mut(b).remove::<Blocked>();
+        world.entity_mut(f).remove::<Blocked>();
+
+        let mut system_state = SystemState::<Graph<Changed<Position>>>::new(&mut world);
+        system_state.get_mut(&mut world);
+        for entity in [a, b, d] {
+            world.get_mut::<Position>(entity).unwrap().0 += 1.;
+        }
+
+        let graph = system_state.get_mut(&mut world);
+        assert_eq!(graph.shortest_path(a, f, |road| road.0), None);
+        assert_eq!(
+            graph.shortest_path(a, d, |road| road.0),
+            Some((vec![a, b, d], 2.))
+        );
+
+        for entity in [a, b, d, f] {
+            world.get_mut::<Position>(entity).unwrap().0 += 1.;
+        }
+
+        let graph = system_state.get_mut(&mut world);
+        assert_eq!(
+            graph.shortest_path(a, f, |road| road.0),
+            Some((vec![a, b, d, f], 3.))
+        );
+    }
+}
@@ -0,0 +1,130 @@
+use super::*;
+use crate::query::QueryState;
+
+// `Ops` borrows a `Query` which only systems hand out. Outside of systems a `Query` is built from
+// a `QueryState` for the duration of a closure so `Ops` can't outlive the world borrow.
+
+impl<Q, F, R> QueryState<(Q, Relations<R>), F>
+where
+    Q: 'static + WorldQuery,
+    F: 'static + ReadOnlyWorldQuery,
+    R: 'static + RelationQuerySet + Send + Sync,
+{
+    /// Calls `func` with read-only [`Ops`] over `world`.
+    ///
+    /// Joins are not supported here since they need another [`Query`] to join with. Use a
+    /// [`SystemState`](crate::system::SystemState) to get both queries when joining outside of
+    /// systems.
+    pub fn ops<'w, T>(
+        &'w mut self,
+        world: &'w World,
+        func: impl FnOnce(
+            Ops<
+                &Query<'w, 'w, (Q, Relations<R>), F>,
+                R::ColsWith<()>,
+                R::ColsWith<Drop>,
+                R::ColsWith<Drop>,
+            >,
+        ) -> T,
+    ) -> T {
+        self.update_archetypes(world);
+        // SAFETY: The query is forced to be read-only and `func` only gets a shared reference.
+        let query = unsafe {
+            Query::new(
+                world,
+                self,
+                world.last_change_tick(),
+                world.read_change_tick(),
+                true,
+            )
+        };
+        func(query.ops())
+    }
+
+    /// Calls `func` with mutable [`Ops`] over `world`.
+    ///
+    /// Like [`QueryState::ops`] this can't join with other queries.
+    pub fn ops_mut<'w, T>(
+        &'w mut self,
+        world: &'w mut World,
+        func: impl FnOnce(
+            Ops<
+                &mut Query<'w, 'w, (Q, Relations<R>), F>,
+                R::ColsWith<()>,
+                R::ColsWith<Drop>,
+                R::ColsWith<Drop>,
+            >,
+        ) -> T,
+    ) -> T {
+        let change_tick = world.change_tick();
+        self.update_archetypes(world);
+        // SAFETY: The world is borrowed mutably for as long as the query lives.
+        let mut query =
+            unsafe { Query::new(world, self, world.last_change_tick(), change_tick, false) };
+        func(query.ops_mut())
+    }
+}
+
+impl World {
+    /// Returns a [`QueryState`] for `Q` and the relations `R` that [`Ops`] can be built from.
+    pub fn relation_query<Q, R>(&mut self) -> QueryState<(Q, Relations<R>)>
+    where
+        Q: WorldQuery,
+        R: RelationQuerySet,
+    {
+        self.query::<(Q, Relations<R>)>()
+    }
+
+    /// Same as [`World::relation_query`] with an additional query filter `F`.
+    pub fn relation_query_filtered<Q, R, F>(&mut self) -> QueryState<(Q, Relations<R>), F>
+    where
+        Q: WorldQuery,
+        R: RelationQuerySet,
+        F: ReadOnlyWorldQuery,
+    {
+        self.query_filtered::<(Q, Relations<R>), F>()
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+    use crate::{self as bevy_ecs, component::TableStorage, prelude::*};
+
+    #[derive(Component)]
+    struct Health(u32);
+
+    struct Child;
+
+    impl Relation for Child {
+        type Storage = TableStorage;
+    }
+
+    #[test]
+    fn ops_outside_systems() {
+        let mut world = World::new();
+        let [a, b, c, d] = [10, 20, 30, 40].map(|hp| world.spawn(Health(hp)).id());
+        world.set_relations_batch([(a, b, Child), (b, c, Child)]);
+
+        let mut state = world.relation_query::<&mut Health, Option<&Child>>();
+        state.ops_mut(&mut world, |ops| {
+            ops.breadth_first::<Child>(a)
+                .for_each(|health, _| health.0 += 1);
+        });
+
+        let mut visited = Vec::new();
+        world
+            .relation_query_filtered::<(Entity, &Health), Option<&Child>, Without<Marker>>()
+            .ops(&world, |ops| {
+                ops.breadth_first::<Child>(b)
+                    .for_each(|(entity, health), _| visited.push((*entity, health.0)));
+            });
+
+        assert_eq!(visited, vec![(b, 21), (c, 31)]);
+        assert_eq!(world.get::<Health>(a).unwrap().0, 11);
+        assert_eq!(world.get::<Health>(d).unwrap().0, 40);
+    }
+
+    #[derive(Component)]
+    struct Marker;
+}
@@ -0,0 +1,260 @@
+use super::*;
+use crate::{query::ReadOnlyWorldQuery, schedule::simple_cycles_in_component};
+use bevy_utils::petgraph::{algo::TarjanScc, prelude::*};
+
+// Topological ordering of relation graphs, using the same algorithms as schedules.
+// Edges point from foster to target so fosters are ordered before their targets. For
+// dependency relations such as `Requires` reverse the order to get build order.
+
+fn relation_graph<'a>(
+    get_edges: impl Fn(Entity) -> Option<&'a Edges>,
+    relation: TypeId,
+    roots: impl IntoIterator<Item = Entity>,
+) -> DiGraphMap<Entity, ()> {
+    let mut graph = DiGraphMap::new();
+    let mut stack = roots.into_iter().collect::<Vec<_>>();
+
+    for root in stack.iter() {
+        graph.add_node(*root);
+    }
+
+    while let Some(entity) = stack.pop() {
+        let Some(edges) = get_edges(entity) else {
+            continue;
+        };
+
+        for target in edges
+            .targets
+            .iter()
+            .filter_map(|targets| targets.get(&relation))
+            .flat_map(|targets| targets.keys())
+        {
+            if get_edges(*target).is_none() {
+                continue;
+            }
+
+            if !graph.contains_node(*target) {
+                stack.push(*target);
+            }
+
+            graph.add_edge(entity, *target, ());
+        }
+    }
+
+    graph
+}
+
+// Strongly connected components in topological order.
+fn components(graph: &DiGraphMap<Entity, ()>) -> Vec<Vec<Entity>> {
+    let mut tarjan_scc = TarjanScc::new();
+    let mut sccs = Vec::new();
+
+    tarjan_scc.run(graph, |scc| sccs.push(scc.to_vec()));
+
+    // Tarjan's SCC algorithm returns components in reverse topological order.
+    sccs.reverse();
+    sccs
+}
+
+fn topsort(graph: &DiGraphMap<Entity, ()>) -> Result<Vec<Entity>, Vec<Vec<Entity>>> {
+    let sccs = components(graph);
+    let mut cycles = Vec::new();
+
+    for scc in sccs.iter() {
+        if scc.len() > 1 || graph.contains_edge(scc[0], scc[0]) {
+            cycles.append(&mut simple_cycles_in_component(graph, scc));
+        }
+    }
+
+    if cycles.is_empty() {
+        Ok(sccs.into_iter().flatten().collect())
+    } else {
+        Err(cycles)
+    }
+}
+
+fn fosters_erased(world: &World, relation: TypeId) -> Vec<Entity> {
+    world
+        .iter_entities()
+        .filter(|entity| {
+            entity.get::<Edges>().map_or(false, |edges| {
+                edges
+                    .targets
+                    .iter()
+                    .any(|targets| targets.contains_key(&relation))
+            })
+        })
+        .map(|entity| entity.id())
+        .collect()
+}
+
+impl World {
+    /// Orders `roots` and everything reachable from them through `R` so every foster comes
+    /// before its targets.
+    ///
+    /// # Errors
+    ///
+    /// Returns every simple cycle as a list of entities if the graph is not acyclic.
+    pub fn topsort<R: Relation>(
+        &self,
+        roots: impl IntoIterator<Item = Entity>,
+    ) -> Result<Vec<Entity>, Vec<Vec<Entity>>> {
+        topsort(&relation_graph(
+            |e| self.get::<Edges>(e),
+            TypeId::of::<Storage<R>>(),
+            roots,
+        ))
+    }
+
+    /// Returns the strongly connected components of `R` in topological order.
+    /// Entities that are in no cycle form a component of their own.
+    pub fn strongly_connected<R: Relation>(&self) -> Vec<Vec<Entity>> {
+        let relation = TypeId::of::<Storage<R>>();
+
+        components(&relation_graph(
+            |e| self.get::<Edges>(e),
+            relation,
+            fosters_erased(self, relation),
+        ))
+    }
+}
+
+impl<F: ReadOnlyWorldQuery> Query<'_, '_, &Edges, F> {
+    /// Orders `roots` and everything reachable from them through `R` so every foster comes
+    /// before its targets. Only entities matched by this query are walked.
+    ///
+    /// # Errors
+    ///
+    /// Returns every simple cycle as a list of entities if the graph is not acyclic.
+    pub fn topsort<R: Relation>(
+        &self,
+        roots: impl IntoIterator<Item = Entity>,
+    ) -> Result<Vec<Entity>, Vec<Vec<Entity>>> {
+        topsort(&relation_graph(
+            |e| self.get(e).ok(),
+            TypeId::of::<Storage<R>>(),
+            roots.into_iter().filter(|root| self.contains(*root)),
+        ))
+    }
+
+    /// Returns the strongly connected components of `R` in topological order.
+    /// Only entities matched by this query are walked, each matched entity with edges of `R`
+    /// is part of a component even if its fosters and targets aren't matched.
+    pub fn strongly_connected<R: Relation>(&self) -> Vec<Vec<Entity>> {
+        let relation = TypeId::of::<Storage<R>>();
+        let roots = self
+            .state
+            .matched_archetype_ids
+            .iter()
+            .flat_map(|id| self.world.archetypes()[*id].entities())
+            .map(|entity| entity.entity())
+            .filter(|entity| {
+                self.get(*entity).map_or(false, |edges| {
+                    edges.targets_of::<R>().next().is_some()
+                        || edges
+                            .fosters
+                            .get(&relation)
+                            .map_or(false, |fosters| !fosters.is_empty())
+                })
+            })
+            .collect::<Vec<_>>();
+
+        components(&relation_graph(|e| self.get(e).ok(), relation, roots))
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+    use crate::{self as bevy_ecs, component::TableStorage, prelude::*, system::SystemState};
+
+    #[derive(Component)]
+    struct Disabled;
+
+    struct Requires;
+
+    impl Relation for Requires {
+        type Storage = TableStorage;
+    }
+
+    fn position(order: &[Entity], entity: Entity) -> usize {
+        order.iter().position(|e| *e == entity).unwrap()
+    }
+
+    fn sorted(mut components: Vec<Vec<Entity>>) -> Vec<Vec<Entity>> {
+        components.iter_mut().for_each(|component| component.sort());
+        components.sort();
+        components
+    }
+
+    #[test]
+    fn topological_order() {
+        let mut world = World::new();
+        let [sword, blade, hilt, ingot, ore, unrelated] = [(); 6].map(|_| world.spawn_empty().id());
+
+        world.set_relations_batch([
+            (sword, blade, Requires),
+            (sword, hilt, Requires),
+            (blade, ingot, Requires),
+            (hilt, ingot, Requires),
+            (ingot, ore, Requires),
+        ]);
+
+        let order = world.topsort::<Requires>([sword]).unwrap();
+        assert_eq!(order.len(), 5);
+        assert!(!order.contains(&unrelated));
+
+        for (foster, target) in [
+            (sword, blade),
+            (sword, hilt),
+            (blade, ingot),
+            (hilt, ingot),
+            (ingot, ore),
+        ] {
+            assert!(position(&order, foster) < position(&order, target));
+        }
+
+        assert_eq!(world.topsort::<Requires>([ingot]), Ok(vec![ingot, ore]));
+        assert_eq!(world.topsort::<Requires>([unrelated]), Ok(vec![unrelated]));
+
+        // Disabled entities are skipped by the query.
+        world.entity_mut(ingot).insert(Disabled);
+
+        let mut system_state = SystemState::<Query<&Edges, Without<Disabled>>>::new(&mut world);
+        let edges = system_state.get(&world);
+        let order = edges.topsort::<Requires>([sword]).unwrap();
+        assert_eq!(order.len(), 3);
+        assert!(!order.contains(&ingot) && !order.contains(&ore));
+
+        // `ore` is matched even though its only foster isn't.
+        assert_eq!(
+            sorted(edges.strongly_connected::<Requires>()),
+            sorted(vec![vec![sword], vec![blade], vec![hilt], vec![ore]])
+        );
+    }
+
+    #[test]
+    fn cycles() {
+        let mut world = World::new();
+        let [a, b, c, d, e] = [(); 5].map(|_| world.spawn_empty().id());
+
+        world.set_relations_batch([
+            (a, b, Requires),
+            (b, c, Requires),
+            (c, a, Requires),
+            (c, d, Requires),
+            (e, e, Requires),
+        ]);
+
+        let cycles = world.topsort::<Requires>([a]).unwrap_err();
+        assert_eq!(cycles.len(), 1);
+        assert_eq!(sorted(cycles), vec![vec![a, b, c]]);
+
+        assert_eq!(world.topsort::<Requires>([e]), Err(vec![vec![e]]));
+
+        let components = world.strongly_connected::<Requires>();
+        let cycle = components.iter().position(|c| c.contains(&a)).unwrap();
+        assert!(cycle < components.iter().position(|c| *c == [d]).unwrap());
+        assert_eq!(sorted(components), vec![vec![a, b, c], vec![d], vec![e]]);
+    }
+}
@@ -192,6 +192,17 @@ where
 {
 }
 
+impl<K, Q> NoFlatten for KeyJoin<'_, K, Q> {}
+
+impl<Q, R, F, Joins, EdgeComb, StorageComb> NoFlatten
+    for Ops<&'_ Query<'_, '_, (Q, Relations<R>), F>, Joins, EdgeComb, StorageComb>
+where
+    Q: 'static + WorldQuery,
+    F: 'static + ReadOnlyWorldQuery,
+    R: RelationQuerySet,
+{
+}
+
 pub trait Flatten<Flattened: Append> {
     type Out: Append;
     fn flatten(self, flattened: Flattened) -> Self::Out;
@@ -16,6 +16,7 @@ pub use self::set::*;
 pub use self::state::*;
 
 pub use self::graph_utils::NodeId;
+pub(crate) use self::graph_utils::simple_cycles_in_component;
 
 #[cfg(test)]
 mod tests {
@@ -274,8 +274,8 @@ use std::{any::TypeId, borrow::Borrow, fmt::Debug};
 /// [`With`]: crate::query::With
 /// [`Without`]: crate::query::Without
 pub struct Query<'world, 'state, Q: 'static + WorldQuery, F: 'static + ReadOnlyWorldQuery = ()> {
-    world: &'world World,
-    state: &'state QueryState<Q, F>,
+    pub(crate) world: &'world World,
+    pub(crate) state: &'state QueryState<Q, F>,
     last_run: Tick,
     this_run: Tick,
     // SAFETY: This is used to ensure that `get_component_mut::<C>` properly fails when a Query writes C
@@ -1,9 +1,10 @@
 use crate::{
     archetype::{Archetype, ArchetypeId, Archetypes},
-    bundle::{Bundle, BundleInfo, BundleInserter, DynamicBundle},
+    bundle::{Bundle, BundleId, BundleInfo, BundleInserter, DynamicBundle},
     change_detection::MutUntyped,
     component::{Component, ComponentId, ComponentTicks, Components, StorageType},
     entity::{Entities, Entity, EntityLocation},
+    query::DebugCheckedUnwrap,
     removal_detection::RemovedComponentEvents,
     storage::Storages,
     world::{Mut, World},
@@ -518,17 +519,49 @@ impl<'w> EntityMut<'w> {
     /// Removes any components in the [`Bundle`] from the entity.
     // TODO: BundleRemover?
     pub fn remove<T: Bundle>(&mut self) -> &mut Self {
+        let bundle_id = self
+            .world
+            .bundles
+            .init_info::<T>(&mut self.world.components, &mut self.world.storages)
+            .id();
+
+        // SAFETY: `bundle_id` was initialized by `Bundles::init_info`
+        unsafe { self.remove_bundle(bundle_id) }
+    }
+
+    /// Removes a dynamic [`Component`] from the entity if it exists.
+    ///
+    /// You should prefer to use the typed API [`EntityMut::remove`] where possible.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the provided [`ComponentId`] does not exist in the [`World`].
+    pub fn remove_by_id(&mut self, component_id: ComponentId) -> &mut Self {
+        let (bundle_info, _) = self
+            .world
+            .bundles
+            .init_component_info(&mut self.world.components, component_id);
+        let bundle_id = bundle_info.id();
+
+        // SAFETY: `bundle_id` was initialized by `Bundles::init_component_info`
+        unsafe { self.remove_bundle(bundle_id) }
+    }
+
+    /// # Safety
+    ///
+    /// `bundle_id` must be initialized in this world's [`Bundles`](crate::bundle::Bundles).
+    unsafe fn remove_bundle(&mut self, bundle_id: BundleId) -> &mut Self {
         let archetypes = &mut self.world.archetypes;
         let storages = &mut self.world.storages;
         let components = &mut self.world.components;
         let entities = &mut self.world.entities;
         let removed_components = &mut self.world.removed_components;
 
-        let bundle_info = self.world.bundles.init_info::<T>(components, storages);
+        let bundle_info = self.world.bundles.get(bundle_id).debug_checked_unwrap();
         let old_location = self.location;
 
         // SAFETY: `archetype_id` exists because it is referenced in the old `EntityLocation` which is valid,
-        // components exist in `bundle_info` because `Bundles::init_info` initializes a `BundleInfo` containing all components of the bundle type `T`
+        // components exist in `bundle_info` because the caller guarantees `bundle_id` was initialized
         let new_archetype_id = unsafe {
             remove_bundle_from_archetype(
                 archetypes,
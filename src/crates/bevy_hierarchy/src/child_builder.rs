@@ -1,21 +1,43 @@
-use crate::{Children, HierarchyEvent, Parent};
+use crate::{ChildOf, Children, HierarchyEvent, Parent};
 use bevy_ecs::{
     bundle::Bundle,
     entity::Entity,
     prelude::Events,
+    relation::{Detach, Set},
     system::{Command, Commands, EntityCommands},
     world::{EntityMut, World},
 };
 use smallvec::SmallVec;
+use std::marker::PhantomData;
 
 // Do not use `world.send_event_batch` as it prints error message when the Events are not available in the world,
 // even though it's a valid use case to execute commands on a world without events. Loading a GLTF file for example
-fn push_events(world: &mut World, events: impl IntoIterator<Item = HierarchyEvent>) {
+pub(crate) fn push_events(world: &mut World, events: impl IntoIterator<Item = HierarchyEvent>) {
     if let Some(mut moved) = world.get_resource_mut::<Events<HierarchyEvent>>() {
         moved.extend(events);
     }
 }
 
+// Every change to `Parent` is mirrored by a `ChildOf` edge from the parent to the child. The
+// components are updated first so the hooks of `ChildOf` find them up to date.
+fn link(world: &mut World, parent: Entity, child: Entity) {
+    Set {
+        foster: parent,
+        target: child,
+        relation: ChildOf,
+    }
+    .write(world);
+}
+
+fn unlink(world: &mut World, parent: Entity, child: Entity) {
+    Detach::<ChildOf> {
+        foster: parent,
+        target: child,
+        _phantom: PhantomData,
+    }
+    .write(world);
+}
+
 fn push_child_unchecked(world: &mut World, parent: Entity, child: Entity) {
     let mut parent = world.entity_mut(parent);
     if let Some(mut children) = parent.get_mut::<Children>() {
@@ -26,15 +48,22 @@ fn push_child_unchecked(world: &mut World, parent: Entity, child: Entity) {
 }
 
 fn update_parent(world: &mut World, child: Entity, new_parent: Entity) -> Option<Entity> {
-    let mut child = world.entity_mut(child);
-    if let Some(mut parent) = child.get_mut::<Parent>() {
+    let mut child_mut = world.entity_mut(child);
+    let previous = if let Some(mut parent) = child_mut.get_mut::<Parent>() {
         let previous = parent.0;
         *parent = Parent(new_parent);
         Some(previous)
     } else {
-        child.insert(Parent(new_parent));
+        child_mut.insert(Parent(new_parent));
         None
+    };
+
+    if let Some(previous) = previous.filter(|previous| *previous != new_parent) {
+        unlink(world, previous, child);
     }
+    link(world, new_parent, child);
+
+    previous
 }
 
 /// Remove child from the parent's [`Children`] component.
@@ -124,6 +153,7 @@ fn remove_children(parent: Entity, children: &[Entity], world: &mut World) {
     for event in &events {
         if let &HierarchyEvent::ChildRemoved { child, .. } = event {
             world.entity_mut(child).remove::<Parent>();
+            unlink(world, parent, child);
         }
     }
     push_events(world, events);
@@ -144,6 +174,7 @@ fn clear_children(parent: Entity, world: &mut World) {
     if let Some(children) = world.entity_mut(parent).take::<Children>() {
         for &child in &children.0 {
             world.entity_mut(child).remove::<Parent>();
+            unlink(world, parent, child);
         }
     }
 }
@@ -401,6 +432,7 @@ impl<'w> WorldChildBuilder<'w> {
     pub fn spawn(&mut self, bundle: impl Bundle + Send + Sync + 'static) -> EntityMut<'_> {
         let entity = self.world.spawn((bundle, Parent(self.parent))).id();
         push_child_unchecked(self.world, self.parent, entity);
+        link(self.world, self.parent, entity);
         push_events(
             self.world,
             [HierarchyEvent::ChildAdded {
@@ -415,6 +447,7 @@ impl<'w> WorldChildBuilder<'w> {
     pub fn spawn_empty(&mut self) -> EntityMut<'_> {
         let entity = self.world.spawn(Parent(self.parent)).id();
         push_child_unchecked(self.world, self.parent, entity);
+        link(self.world, self.parent, entity);
         push_events(
             self.world,
             [HierarchyEvent::ChildAdded {
@@ -535,6 +568,7 @@ impl<'w> BuildWorldChildren for EntityMut<'w> {
         if let Some(parent) = self.take::<Parent>().map(|p| p.get()) {
             self.world_scope(|world| {
                 remove_from_children(world, parent, child);
+                unlink(world, parent, child);
                 push_events(world, [HierarchyEvent::ChildRemoved { child, parent }]);
             });
         }
@@ -546,16 +580,18 @@ impl<'w> BuildWorldChildren for EntityMut<'w> {
 mod tests {
     use super::{BuildChildren, BuildWorldChildren};
     use crate::{
-        components::{Children, Parent},
+        components::{ChildOf, Children, Parent},
         HierarchyEvent::{self, ChildAdded, ChildMoved, ChildRemoved},
     };
     use smallvec::{smallvec, SmallVec};
+    use std::marker::PhantomData;
 
     use bevy_ecs::{
         component::Component,
         entity::Entity,
         event::Events,
-        system::{CommandQueue, Commands},
+        relation::{Set, UnSet},
+        system::{Command, CommandQueue, Commands},
         world::World,
     };
 
@@ -994,6 +1030,62 @@ mod tests {
         assert!(world.get::<Children>(parent2).is_none());
     }
 
+    #[test]
+    fn relation_commands_send_events() {
+        let world = &mut World::new();
+        world.insert_resource(Events::<HierarchyEvent>::default());
+
+        let [a, b, c] = std::array::from_fn(|_| world.spawn_empty().id());
+
+        let set = |world: &mut World, parent| {
+            Set {
+                foster: parent,
+                target: c,
+                relation: ChildOf,
+            }
+            .write(world);
+        };
+
+        set(world, a);
+        assert_events(
+            world,
+            &[ChildAdded {
+                child: c,
+                parent: a,
+            }],
+        );
+
+        // Setting the same edge again changes nothing.
+        set(world, a);
+        assert_events(world, &[]);
+
+        set(world, b);
+        assert_parent(world, c, Some(b));
+        assert_events(
+            world,
+            &[ChildMoved {
+                child: c,
+                previous_parent: a,
+                new_parent: b,
+            }],
+        );
+
+        UnSet::<ChildOf> {
+            foster: b,
+            target: c,
+            _phantom: PhantomData,
+        }
+        .write(world);
+        assert!(world.get_entity(c).is_none());
+        assert_events(
+            world,
+            &[ChildRemoved {
+                child: c,
+                parent: b,
+            }],
+        );
+    }
+
     #[test]
     fn regression_push_children_same_archetype() {
         let mut world = World::new();
@@ -0,0 +1,114 @@
+use crate::{
+    child_builder::push_events,
+    components::{Children, Parent},
+    HierarchyEvent,
+};
+use bevy_ecs::{
+    component::TableStorage,
+    entity::Entity,
+    relation::{DespawnPolicy, Detach, ReflectRelation, Relation, Set},
+    system::Command,
+    world::World,
+};
+use bevy_reflect::Reflect;
+use std::marker::PhantomData;
+
+/// Relation backing [`Parent`] and [`Children`].
+///
+/// Edges point from the parent to the child so despawning a parent despawns its children through
+/// [`DespawnPolicy::RecursiveDespawn`]. [`Parent`] and [`Children`] are derived from the edges:
+/// setting, unsetting or despawning through relation commands updates them, and setting an edge
+/// to a child that already has a parent moves it. Relation commands send the same
+/// [`HierarchyEvent`]s as hierarchy builders such as [`BuildChildren`] and [`BuildWorldChildren`].
+///
+/// Hierarchies built by inserting [`Parent`] and [`Children`] directly, for example by scenes,
+/// have no edges until [`link_child_of`] is called.
+///
+/// [`BuildChildren`]: crate::BuildChildren
+/// [`BuildWorldChildren`]: crate::BuildWorldChildren
+/// [`HierarchyEvent`]: crate::HierarchyEvent
+#[derive(Debug, Default, Reflect)]
+#[reflect(Relation)]
+pub struct ChildOf;
+
+impl Relation for ChildOf {
+    type Storage = TableStorage;
+    const DESPAWN_POLICY: DespawnPolicy = DespawnPolicy::RecursiveDespawn;
+
+    fn on_set(world: &mut World, parent: Entity, child: Entity) {
+        let previous = world.get::<Parent>(child).map(Parent::get);
+
+        // Hierarchy builders update `Parent` before setting the edge and send their own events.
+        if previous != Some(parent) {
+            world.entity_mut(child).insert(Parent(parent));
+
+            // A child has a single parent so the edge from the previous one is removed.
+            if let Some(previous) = previous {
+                Detach::<ChildOf> {
+                    foster: previous,
+                    target: child,
+                    _phantom: PhantomData,
+                }
+                .write(world);
+
+                forget_child(world, previous, child);
+                push_events(
+                    world,
+                    [HierarchyEvent::ChildMoved {
+                        child,
+                        previous_parent: previous,
+                        new_parent: parent,
+                    }],
+                );
+            } else {
+                push_events(world, [HierarchyEvent::ChildAdded { child, parent }]);
+            }
+        }
+
+        let mut parent_mut = world.entity_mut(parent);
+        match parent_mut.get_mut::<Children>() {
+            Some(mut children) if !children.contains(&child) => children.0.push(child),
+            Some(_) => {}
+            None => {
+                parent_mut.insert(Children::from_entities(&[child]));
+            }
+        }
+    }
+
+    fn on_remove(world: &mut World, parent: Entity, child: Entity) {
+        if let Some(mut child_mut) = world.get_entity_mut(child) {
+            if child_mut.get::<Parent>().map(Parent::get) == Some(parent) {
+                child_mut.remove::<Parent>();
+                push_events(world, [HierarchyEvent::ChildRemoved { child, parent }]);
+            }
+        }
+
+        forget_child(world, parent, child);
+    }
+}
+
+// Like despawning, removing an edge leaves an empty `Children` on the parent.
+fn forget_child(world: &mut World, parent: Entity, child: Entity) {
+    if let Some(mut children) = world.get_mut::<Children>(parent) {
+        children.0.retain(|c| *c != child);
+    }
+}
+
+/// Sets the [`ChildOf`] edges of `entities` from their [`Parent`]. Entities that already have
+/// an edge from their parent are skipped, so their hooks don't run again.
+pub fn link_child_of(world: &mut World, entities: impl IntoIterator<Item = Entity>) {
+    for child in entities {
+        let Some(parent) = world.get::<Parent>(child).map(Parent::get) else {
+            continue;
+        };
+
+        if world.get_entity(parent).is_some() && !world.is_reachable::<ChildOf>(parent, child) {
+            Set {
+                foster: parent,
+                target: child,
+                relation: ChildOf,
+            }
+            .write(world);
+        }
+    }
+}
@@ -1,5 +1,7 @@
+mod child_of;
 mod children;
 mod parent;
 
+pub use child_of::{link_child_of, ChildOf};
 pub use children::Children;
 pub use parent::Parent;
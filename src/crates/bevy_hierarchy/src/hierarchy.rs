@@ -1,6 +1,7 @@
 use crate::components::{Children, Parent};
 use bevy_ecs::{
     entity::Entity,
+    relation::{Edges, OrphaningDespawn},
     system::{Command, EntityCommands},
     world::{EntityMut, World},
 };
@@ -21,12 +22,19 @@ pub struct DespawnChildrenRecursive {
 }
 
 /// Function for despawning an entity and all its children
+///
+/// Only [`Children`] are walked. Relation edges of the despawned entities are removed with
+/// [`OrphaningDespawn`], use [`CheckedDespawn`] to apply the despawn policies of other relations.
+///
+/// [`CheckedDespawn`]: bevy_ecs::relation::CheckedDespawn
 pub fn despawn_with_children_recursive(world: &mut World, entity: Entity) {
     // first, make the entity's own parent forget about it
     if let Some(parent) = world.get::<Parent>(entity).map(|parent| parent.0) {
         if let Some(mut children) = world.get_mut::<Children>(parent) {
             children.0.retain(|c| *c != entity);
         }
+        // removing the `ChildOf` edge would send a `HierarchyEvent` otherwise
+        world.entity_mut(entity).remove::<Parent>();
     }
 
     // then despawn the entity and all of its children
@@ -41,7 +49,10 @@ fn despawn_with_children_recursive_inner(world: &mut World, entity: Entity) {
         }
     }
 
-    if !world.despawn(entity) {
+    // `ChildOf` and other edges are removed so no entity keeps edges to a despawned one.
+    if world.get::<Edges>(entity).is_some() {
+        OrphaningDespawn { entity }.write(world);
+    } else if !world.despawn(entity) {
         debug!("Failed to despawn entity {:?}", entity);
     }
 }
@@ -138,13 +149,18 @@ impl<'w> DespawnRecursiveExt for EntityMut<'w> {
 #[cfg(test)]
 mod tests {
     use bevy_ecs::{
-        component::Component,
-        system::{CommandQueue, Commands},
+        component::{Component, TableStorage},
+        relation::{CheckedDespawn, DespawnPolicy, Relation, Set, UnSet},
+        system::{Command, CommandQueue, Commands},
         world::World,
     };
+    use std::marker::PhantomData;
 
     use super::DespawnRecursiveExt;
-    use crate::{child_builder::BuildChildren, components::Children};
+    use crate::{
+        child_builder::{BuildChildren, BuildWorldChildren},
+        components::{link_child_of, ChildOf, Children, Parent},
+    };
 
     #[derive(Component, Clone, Copy, PartialEq, Eq, Ord, PartialOrd, Debug)]
     struct Idx(u32);
@@ -152,6 +168,13 @@ mod tests {
     #[derive(Component, Clone, PartialEq, Eq, Ord, PartialOrd, Debug)]
     struct N(String);
 
+    struct Owns;
+
+    impl Relation for Owns {
+        type Storage = TableStorage;
+        const DESPAWN_POLICY: DespawnPolicy = DespawnPolicy::RecursiveDespawn;
+    }
+
     #[test]
     fn despawn_recursive() {
         let mut world = World::default();
@@ -226,4 +249,114 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn child_of_relation() {
+        let mut world = World::default();
+        let root = world.spawn_empty().id();
+        let other = world.spawn_empty().id();
+        let mut children = Vec::new();
+
+        world.entity_mut(root).with_children(|parent| {
+            children.push(parent.spawn_empty().id());
+            children.push(parent.spawn(Idx(0)).id());
+        });
+
+        let grandchild = world.spawn_empty().id();
+        world.entity_mut(children[1]).add_child(grandchild);
+
+        let mut descendants = world.transitive_targets::<ChildOf>(root);
+        descendants.sort();
+        assert_eq!(descendants, vec![children[0], children[1], grandchild]);
+
+        // Removing a parent keeps the child alive.
+        world.entity_mut(children[0]).remove_parent();
+        assert!(world.get_entity(children[0]).is_some());
+        assert!(!world.is_reachable::<ChildOf>(root, children[0]));
+
+        world.entity_mut(children[1]).set_parent(other);
+        assert_eq!(world.get::<Parent>(children[1]).unwrap().get(), other);
+        assert!(!world.is_reachable::<ChildOf>(root, grandchild));
+        assert!(world.is_reachable::<ChildOf>(other, grandchild));
+
+        // Despawning through the relation takes the whole subtree.
+        CheckedDespawn { entity: other }.write(&mut world);
+        assert!(world.get_entity(children[1]).is_none());
+        assert!(world.get_entity(grandchild).is_none());
+
+        world.entity_mut(root).push_children(&[children[0]]);
+        world.entity_mut(root).despawn_recursive();
+        assert!(world.get_entity(children[0]).is_none());
+    }
+
+    #[test]
+    fn relation_commands_update_hierarchy() {
+        let mut world = World::default();
+        let [parent, other, child, grandchild] = [(); 4].map(|_| world.spawn_empty().id());
+
+        world.set_relations_batch([(parent, child, ChildOf), (child, grandchild, ChildOf)]);
+        assert_eq!(world.get::<Parent>(child).unwrap().get(), parent);
+        assert_eq!(&**world.get::<Children>(parent).unwrap(), &[child]);
+
+        // Setting an edge from another parent moves the child.
+        Set {
+            foster: other,
+            target: child,
+            relation: ChildOf,
+        }
+        .write(&mut world);
+        assert_eq!(world.get::<Parent>(child).unwrap().get(), other);
+        assert!(world.get::<Children>(parent).unwrap().is_empty());
+        assert!(!world.is_reachable::<ChildOf>(parent, child));
+
+        // Despawning a child through the relation updates its parent.
+        CheckedDespawn { entity: grandchild }.write(&mut world);
+        assert!(world.get::<Children>(child).unwrap().is_empty());
+
+        UnSet::<ChildOf> {
+            foster: other,
+            target: child,
+            _phantom: PhantomData,
+        }
+        .write(&mut world);
+        assert!(world.get_entity(child).is_none());
+        assert!(world.get::<Children>(other).unwrap().is_empty());
+    }
+
+    #[test]
+    fn link_inserted_hierarchy() {
+        let mut world = World::default();
+        let parent = world.spawn_empty().id();
+        let child = world.spawn(Parent(parent)).id();
+        world
+            .entity_mut(parent)
+            .insert(Children::from_entities(&[child]));
+
+        link_child_of(&mut world, [child]);
+        assert!(world.is_reachable::<ChildOf>(parent, child));
+        assert_eq!(&**world.get::<Children>(parent).unwrap(), &[child]);
+
+        world.entity_mut(parent).despawn_recursive();
+        assert!(world.get_entity(child).is_none());
+    }
+
+    #[test]
+    fn despawn_recursive_only_walks_children() {
+        let mut world = World::default();
+        let [parent, child, item, owner] = [(); 4].map(|_| world.spawn_empty().id());
+
+        world.entity_mut(parent).add_child(child);
+        world.set_relations_batch([(child, item, Owns), (owner, child, Owns)]);
+
+        world.entity_mut(parent).despawn_recursive();
+        assert!(world.get_entity(child).is_none());
+
+        // Other relations aren't cascaded but their edges are removed.
+        assert!(world.get_entity(item).is_some());
+        assert!(world.get_entity(owner).is_some());
+        assert!(world.transitive_targets::<Owns>(owner).is_empty());
+
+        CheckedDespawn { entity: owner }.write(&mut world);
+        assert!(world.get_entity(item).is_some());
+    }
 }
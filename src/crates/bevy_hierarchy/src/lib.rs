@@ -19,6 +19,9 @@ pub use events::*;
 mod valid_parent_check_plugin;
 pub use valid_parent_check_plugin::*;
 
+mod valid_relation_check_plugin;
+pub use valid_relation_check_plugin::*;
+
 mod query_extension;
 pub use query_extension::*;
 
@@ -27,7 +30,7 @@ pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
         child_builder::*, components::*, hierarchy::*, query_extension::*, HierarchyPlugin,
-        ValidParentCheckPlugin,
+        ValidParentCheckPlugin, ValidRelationCheckPlugin,
     };
 }
 
@@ -41,6 +44,7 @@ impl Plugin for HierarchyPlugin {
     fn build(&self, app: &mut App) {
         app.register_type::<Children>()
             .register_type::<Parent>()
+            .register_type::<ChildOf>()
             .register_type::<smallvec::SmallVec<[bevy_ecs::entity::Entity; 8]>>()
             .add_event::<HierarchyEvent>();
     }
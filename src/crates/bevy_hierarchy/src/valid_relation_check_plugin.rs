@@ -0,0 +1,94 @@
+use std::marker::PhantomData;
+
+use bevy_app::{App, Last, Plugin};
+use bevy_ecs::{prelude::*, relation::Relation};
+use bevy_log::{error, warn, Level};
+use bevy_utils::get_short_name;
+
+/// Configures [`check_relation_integrity<R>`].
+///
+/// This resource is added by [`ValidRelationCheckPlugin<R>`].
+/// Reports are enabled on debug builds and disabled in release builds by default,
+/// you can update this resource at runtime to change the default behavior.
+#[derive(Resource)]
+pub struct ReportRelationIssue<R> {
+    /// Whether to run [`check_relation_integrity<R>`].
+    pub enabled: bool,
+    /// Issues are logged as errors at [`Level::ERROR`] and as warnings otherwise.
+    pub level: Level,
+    /// Whether to repair issues after reporting them.
+    pub repair: bool,
+    _relation: PhantomData<fn(R)>,
+}
+
+impl<R> ReportRelationIssue<R> {
+    /// Constructs a new object
+    pub fn new(enabled: bool, level: Level, repair: bool) -> Self {
+        ReportRelationIssue {
+            enabled,
+            level,
+            repair,
+            _relation: PhantomData,
+        }
+    }
+}
+
+impl<R> Default for ReportRelationIssue<R> {
+    fn default() -> Self {
+        Self::new(cfg!(debug_assertions), Level::WARN, false)
+    }
+}
+
+/// System to log every broken invariant of relation `R` and optionally repair it.
+///
+/// Relation commands expect the edges of a foster and its targets to agree.
+/// A single inconsistent edge panics the next command that touches it,
+/// see [`RelationIssue`](bevy_ecs::relation::RelationIssue) for what is checked.
+pub fn check_relation_integrity<R: Relation>(world: &mut World) {
+    let issues = world.check_relation::<R>();
+
+    if issues.is_empty() {
+        return;
+    }
+
+    let report = world.resource::<ReportRelationIssue<R>>();
+    let repair = report.repair;
+    let ty_name = get_short_name(std::any::type_name::<R>());
+
+    for issue in issues.iter() {
+        if report.level == Level::ERROR {
+            error!("{ty_name} relation is inconsistent: {issue}");
+        } else {
+            warn!("{ty_name} relation is inconsistent: {issue}");
+        }
+    }
+
+    if repair {
+        world.repair_relation::<R>(&issues);
+    }
+}
+
+/// Run criteria that only allows running when [`ReportRelationIssue<R>`] is enabled.
+pub fn on_relation_reports_enabled<R: Relation>(report: Res<ReportRelationIssue<R>>) -> bool {
+    report.enabled
+}
+
+/// Log and optionally repair every broken invariant of relation `R`.
+///
+/// See [`check_relation_integrity`] for details.
+pub struct ValidRelationCheckPlugin<R: Relation>(PhantomData<fn() -> R>);
+
+impl<R: Relation> Default for ValidRelationCheckPlugin<R> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<R: Relation> Plugin for ValidRelationCheckPlugin<R> {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ReportRelationIssue<R>>().add_systems(
+            Last,
+            check_relation_integrity::<R>.run_if(on_relation_reports_enabled::<R>),
+        );
+    }
+}
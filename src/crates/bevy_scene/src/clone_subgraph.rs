@@ -0,0 +1,510 @@
+use std::any::TypeId;
+
+use bevy_app::AppTypeRegistry;
+use bevy_ecs::{
+    entity::EntityMap,
+    prelude::Entity,
+    reflect::{ReflectComponent, ReflectMapEntities},
+    relation::{DespawnPolicy, ReflectRelation, Relation},
+    system::Command,
+    world::{FromWorld, World},
+};
+use bevy_hierarchy::{Children, Parent};
+use bevy_reflect::{FromType, Reflect, ReflectRef, TypeRegistry};
+use bevy_utils::{
+    tracing::{error, warn},
+    HashSet,
+};
+
+/// What to do with the edges of a cloned subgraph that point to entities outside of it.
+#[derive(Debug, Default)]
+pub enum ExternalEdgePolicy {
+    /// The clone keeps pointing at the same entity as the original, except for edges of
+    /// relations with [`DespawnPolicy::RecursiveDespawn`] or [`Relation::EXCLUSIVE`], which aren't
+    /// cloned so despawning the clone can't despawn or take over entities outside of it.
+    #[default]
+    Keep,
+    /// The edge isn't cloned. Entity references in copied components aren't edges, references
+    /// to entities outside of the subgraph are kept as they are.
+    Drop,
+    /// The clone points at the entity the target is mapped to, edges to targets missing from
+    /// the map aren't cloned.
+    Retarget(EntityMap),
+}
+
+/// Clones `root` and every entity reachable from it through the followed relations.
+///
+/// Components registered with `#[reflect(Component)]` in the [`AppTypeRegistry`] are copied to
+/// the clones, and entity references in components registered with `#[reflect(MapEntities)]`
+/// are mapped to the clones. Edges of every relation registered with `#[reflect(Relation)]` are
+/// recreated between the clones in the same order, edges leaving the subgraph follow the
+/// [`ExternalEdgePolicy`]. Edges pointing into the subgraph from outside aren't cloned.
+/// [`Parent`] and [`Children`] aren't copied but derived from the cloned
+/// [`ChildOf`](bevy_hierarchy::ChildOf) edges, so they never point outside of the subgraph.
+///
+/// References to entities outside of the subgraph are found by walking the reflected fields of
+/// components. Entities inside opaque reflected values can't be found, so if such a value points
+/// outside of the subgraph mapping its component fails and it keeps pointing at the originals.
+///
+/// The clone of `root` is `clone`, which should be an empty entity such as one reserved with
+/// [`Commands::spawn_empty`](bevy_ecs::system::Commands::spawn_empty).
+/// ```
+/// # use bevy_scene::{CloneSubgraph, ExternalEdgePolicy};
+/// # use bevy_app::AppTypeRegistry;
+/// # use bevy_ecs::{
+/// #     component::TableStorage, relation::{ReflectRelation, Relation}, system::Command,
+/// #     world::World,
+/// # };
+/// # use bevy_reflect::Reflect;
+/// #[derive(Reflect, Default)]
+/// #[reflect(Relation)]
+/// struct MountedOn;
+///
+/// impl Relation for MountedOn {
+///     type Storage = TableStorage;
+/// }
+///
+/// # let mut world = World::default();
+/// # world.init_resource::<AppTypeRegistry>();
+/// # world.resource::<AppTypeRegistry>().write().register::<MountedOn>();
+/// let vehicle = world.spawn_empty().id();
+/// let turret = world.spawn_empty().id();
+/// world.set_relations_batch([(vehicle, turret, MountedOn)]);
+///
+/// let clone = world.spawn_empty().id();
+/// CloneSubgraph::new(vehicle, clone)
+///     .follow::<MountedOn>()
+///     .external_edges(ExternalEdgePolicy::Drop)
+///     .write(&mut world);
+///
+/// assert_eq!(world.transitive_targets::<MountedOn>(clone).len(), 1);
+/// ```
+pub struct CloneSubgraph {
+    root: Entity,
+    clone: Entity,
+    follow: Vec<ReflectRelation>,
+    external_edges: ExternalEdgePolicy,
+}
+
+impl CloneSubgraph {
+    /// Clones `root` into `clone` without following any relation.
+    pub fn new(root: Entity, clone: Entity) -> Self {
+        Self {
+            root,
+            clone,
+            follow: Vec::new(),
+            external_edges: ExternalEdgePolicy::default(),
+        }
+    }
+
+    /// Also clones every target of relation `R` of the cloned entities.
+    pub fn follow<R: Relation + Reflect + FromWorld>(mut self) -> Self {
+        self.follow
+            .push(<ReflectRelation as FromType<R>>::from_type());
+        self
+    }
+
+    /// Sets what happens to edges pointing outside of the subgraph.
+    pub fn external_edges(mut self, policy: ExternalEdgePolicy) -> Self {
+        self.external_edges = policy;
+        self
+    }
+}
+
+impl Command for CloneSubgraph {
+    fn write(self, world: &mut World) {
+        if world.get_entity(self.root).is_none() {
+            warn!("Could not clone {:?} because it doesn't exist", self.root);
+            return;
+        }
+
+        let type_registry = world.resource::<AppTypeRegistry>().clone();
+        let type_registry = type_registry.read();
+
+        let mut members = vec![self.root];
+        let mut visited = HashSet::from_iter([self.root]);
+        let mut frontier = vec![self.root];
+        while !frontier.is_empty() {
+            let mut next = Vec::new();
+            for foster in frontier {
+                for reflect_relation in &self.follow {
+                    for (target, _) in reflect_relation.targets(world, foster) {
+                        if visited.insert(target) {
+                            next.push(target);
+                        }
+                    }
+                }
+            }
+
+            members.extend(next.iter().copied());
+            frontier = next;
+        }
+
+        let mut clones = EntityMap::default();
+        clones.insert(self.root, self.clone);
+        for &member in &members[1..] {
+            clones.insert(member, world.spawn_empty().id());
+        }
+
+        // `ReflectComponent::copy` needs two worlds, so the values are cloned out of the
+        // original before being inserted into the clone.
+        let mut copied = HashSet::new();
+        let mut references = HashSet::new();
+        for &member in &members {
+            let entity = world.entity(member);
+            let mut components = Vec::new();
+            for component_id in entity.archetype().components() {
+                let Some(type_id) = world
+                    .components()
+                    .get_info(component_id)
+                    .and_then(|info| info.type_id())
+                else {
+                    continue;
+                };
+
+                // Derived from the `ChildOf` edges recreated below.
+                if type_id == TypeId::of::<Parent>() || type_id == TypeId::of::<Children>() {
+                    continue;
+                }
+
+                let Some(registration) = type_registry.get(type_id) else {
+                    continue;
+                };
+
+                if let Some(component) = registration
+                    .data::<ReflectComponent>()
+                    .and_then(|reflect_component| reflect_component.reflect(entity))
+                {
+                    if registration.data::<ReflectMapEntities>().is_some() {
+                        collect_entities(component, &mut references);
+                    }
+                    components.push((type_id, component.clone_value()));
+                }
+            }
+
+            let mut clone = world.entity_mut(clones.get(member).unwrap());
+            for (type_id, component) in components {
+                let reflect_component = type_registry
+                    .get(type_id)
+                    .and_then(|registration| registration.data::<ReflectComponent>())
+                    .unwrap();
+                reflect_component.apply_or_insert(&mut clone, &*component);
+                copied.insert(type_id);
+            }
+        }
+
+        self.map_entities(world, &type_registry, &copied, &references, &clones);
+
+        for registration in type_registry.iter() {
+            let Some(reflect_relation) = registration.data::<ReflectRelation>() else {
+                continue;
+            };
+
+            let owns_targets = reflect_relation.exclusive()
+                || matches!(
+                    reflect_relation.despawn_policy(),
+                    DespawnPolicy::RecursiveDespawn
+                );
+
+            for &member in &members {
+                let edges = reflect_relation
+                    .targets(world, member)
+                    .into_iter()
+                    .filter_map(|(target, relation)| {
+                        let target = match (clones.get(target), &self.external_edges) {
+                            (Ok(clone), _) => clone,
+                            (_, ExternalEdgePolicy::Keep) if !owns_targets => target,
+                            (_, ExternalEdgePolicy::Keep) => return None,
+                            (_, ExternalEdgePolicy::Drop) => return None,
+                            (_, ExternalEdgePolicy::Retarget(map)) => map.get(target).ok()?,
+                        };
+                        Some((target, relation.clone_value()))
+                    })
+                    .collect::<Vec<_>>();
+
+                let foster = clones.get(member).unwrap();
+                for (target, relation) in edges {
+                    reflect_relation.set(world, foster, target, &*relation);
+                }
+            }
+        }
+    }
+}
+
+impl CloneSubgraph {
+    // References to members are mapped to their clones. References leaving the subgraph can't
+    // be dropped from a component, so they're retargeted or kept as they are, even when they
+    // point at despawned entities.
+    fn map_entities(
+        &self,
+        world: &mut World,
+        type_registry: &TypeRegistry,
+        copied: &HashSet<TypeId>,
+        references: &HashSet<Entity>,
+        clones: &EntityMap,
+    ) {
+        let reflect_map_entities = copied
+            .iter()
+            .filter_map(|type_id| type_registry.get(*type_id)?.data::<ReflectMapEntities>())
+            .collect::<Vec<_>>();
+
+        if reflect_map_entities.is_empty() {
+            return;
+        }
+
+        // Every member is mapped, even ones `collect_entities` can't find inside opaque values.
+        let mut entity_map = EntityMap::default();
+        for (member, clone) in clones.iter() {
+            entity_map.insert(member, clone);
+        }
+
+        for &entity in references {
+            if clones.get(entity).is_ok() {
+                continue;
+            }
+
+            let mapped = match &self.external_edges {
+                ExternalEdgePolicy::Retarget(retarget) => retarget.get(entity).unwrap_or(entity),
+                _ => entity,
+            };
+            entity_map.insert(entity, mapped);
+        }
+
+        let clones = clones.values().collect::<Vec<_>>();
+        for reflect_map_entities in reflect_map_entities {
+            if let Err(error) =
+                reflect_map_entities.map_specific_entities(world, &entity_map, &clones)
+            {
+                error!("Could not map entities of a cloned subgraph: {error}");
+            }
+        }
+    }
+}
+
+// Entities referenced anywhere inside `value`.
+fn collect_entities(value: &dyn Reflect, entities: &mut HashSet<Entity>) {
+    if let Some(entity) = value.downcast_ref::<Entity>() {
+        entities.insert(*entity);
+        return;
+    }
+
+    match value.reflect_ref() {
+        ReflectRef::Struct(value) => {
+            for field in value.iter_fields() {
+                collect_entities(field, entities);
+            }
+        }
+        ReflectRef::TupleStruct(value) => {
+            for field in value.iter_fields() {
+                collect_entities(field, entities);
+            }
+        }
+        ReflectRef::Tuple(value) => {
+            for field in value.iter_fields() {
+                collect_entities(field, entities);
+            }
+        }
+        ReflectRef::List(value) => {
+            for item in value.iter() {
+                collect_entities(item, entities);
+            }
+        }
+        ReflectRef::Array(value) => {
+            for item in value.iter() {
+                collect_entities(item, entities);
+            }
+        }
+        ReflectRef::Map(value) => {
+            for (key, item) in value.iter() {
+                collect_entities(key, entities);
+                collect_entities(item, entities);
+            }
+        }
+        ReflectRef::Enum(value) => {
+            for field in value.iter_fields() {
+                collect_entities(field.value(), entities);
+            }
+        }
+        ReflectRef::Value(_) => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::AppTypeRegistry;
+    use bevy_ecs::{
+        component::{Component, TableStorage},
+        entity::{Entity, EntityMap, MapEntities, MapEntitiesError},
+        reflect::{ReflectComponent, ReflectMapEntities},
+        relation::{ReflectRelation, Relation},
+        system::Command,
+        world::{FromWorld, World},
+    };
+    use bevy_hierarchy::{BuildWorldChildren, ChildOf, Children, DespawnRecursiveExt, Parent};
+    use bevy_reflect::Reflect;
+
+    use super::{CloneSubgraph, ExternalEdgePolicy};
+
+    #[derive(Component, Reflect, Default, Eq, PartialEq, Debug)]
+    #[reflect(Component)]
+    struct Label(String);
+
+    // An entity reference `collect_entities` can't see into.
+    #[derive(Component, Reflect, Clone, Copy)]
+    #[reflect_value(Component, MapEntities)]
+    struct Aim(Entity);
+
+    impl FromWorld for Aim {
+        fn from_world(_world: &mut World) -> Self {
+            Self(Entity::PLACEHOLDER)
+        }
+    }
+
+    impl MapEntities for Aim {
+        fn map_entities(&mut self, entity_map: &EntityMap) -> Result<(), MapEntitiesError> {
+            self.0 = entity_map.get(self.0)?;
+            Ok(())
+        }
+    }
+
+    #[derive(Reflect, Default)]
+    #[reflect(Relation)]
+    struct MountedOn;
+
+    impl Relation for MountedOn {
+        type Storage = TableStorage;
+    }
+
+    #[derive(Reflect, Default)]
+    #[reflect(Relation)]
+    struct Targeting;
+
+    impl Relation for Targeting {
+        type Storage = TableStorage;
+    }
+
+    fn create_world() -> World {
+        let mut world = World::default();
+        let atr = AppTypeRegistry::default();
+        {
+            let mut registry = atr.write();
+            registry.register::<Label>();
+            registry.register::<Aim>();
+            registry.register::<MountedOn>();
+            registry.register::<Targeting>();
+            registry.register::<ChildOf>();
+            registry.register::<Parent>();
+            registry.register::<Children>();
+        }
+        world.insert_resource(atr);
+        world
+    }
+
+    fn label(world: &World, entity: Entity) -> &str {
+        &world.get::<Label>(entity).unwrap().0
+    }
+
+    #[test]
+    fn clone_subgraph() {
+        let mut world = create_world();
+        let [vehicle, turret, enemy, decoy] = ["vehicle", "turret", "enemy", "decoy"]
+            .map(|name| world.spawn(Label(name.to_string())).id());
+        world.set_relations_batch([(vehicle, turret, MountedOn)]);
+        world.set_relations_batch([(turret, vehicle, Targeting), (turret, enemy, Targeting)]);
+
+        let mut retarget = EntityMap::default();
+        retarget.insert(enemy, decoy);
+
+        for (policy, external) in [
+            (ExternalEdgePolicy::Keep, vec![enemy]),
+            (ExternalEdgePolicy::Drop, vec![]),
+            (ExternalEdgePolicy::Retarget(retarget), vec![decoy]),
+        ] {
+            let clone = world.spawn_empty().id();
+            CloneSubgraph::new(vehicle, clone)
+                .follow::<MountedOn>()
+                .external_edges(policy)
+                .write(&mut world);
+
+            assert_eq!(label(&world, clone), "vehicle");
+            let [turret_clone] = world.transitive_targets::<MountedOn>(clone)[..] else {
+                panic!("turret should be cloned once");
+            };
+            assert_ne!(turret_clone, turret);
+            assert_eq!(label(&world, turret_clone), "turret");
+
+            // Internal edges point at the clones, external ones follow the policy.
+            let mut targeting = world.transitive_targets::<Targeting>(turret_clone);
+            targeting.retain(|target| *target != clone);
+            assert_eq!(targeting, external);
+        }
+
+        // The originals are untouched and only the vehicle and turret were cloned.
+        assert_eq!(world.transitive_targets::<MountedOn>(vehicle), vec![turret]);
+        assert_eq!(world.query::<&Label>().iter(&world).count(), 10);
+    }
+
+    #[test]
+    fn clone_maps_opaque_references() {
+        let mut world = create_world();
+        let [vehicle, turret] = [(); 2].map(|_| world.spawn_empty().id());
+        world.set_relations_batch([(vehicle, turret, MountedOn)]);
+        world.entity_mut(vehicle).insert(Aim(turret));
+
+        let clone = world.spawn_empty().id();
+        CloneSubgraph::new(vehicle, clone)
+            .follow::<MountedOn>()
+            .write(&mut world);
+
+        let turret_clone = world.transitive_targets::<MountedOn>(clone)[0];
+        assert_eq!(world.get::<Aim>(clone).unwrap().0, turret_clone);
+    }
+
+    #[test]
+    fn clone_hierarchy() {
+        let mut world = create_world();
+        let root = world.spawn(Label("root".to_string())).id();
+        let child = world.spawn(Label("child".to_string())).id();
+        world.entity_mut(root).add_child(child);
+
+        let clone = world.spawn_empty().id();
+        CloneSubgraph::new(root, clone)
+            .follow::<ChildOf>()
+            .write(&mut world);
+
+        // Entity references in components are mapped to the clones.
+        let child_clone = world.get::<Children>(clone).unwrap()[0];
+        assert_ne!(child_clone, child);
+        assert_eq!(world.get::<Parent>(child_clone).unwrap().get(), clone);
+        assert_eq!(label(&world, child_clone), "child");
+        assert_eq!(
+            world.transitive_targets::<ChildOf>(clone),
+            vec![child_clone]
+        );
+    }
+
+    #[test]
+    fn despawning_clone_keeps_unfollowed_children() {
+        let mut world = create_world();
+        let parent = world.spawn(Label("parent".to_string())).id();
+        let root = world.spawn(Label("root".to_string())).id();
+        let child = world.spawn(Label("child".to_string())).id();
+        world.entity_mut(parent).add_child(root);
+        world.entity_mut(root).add_child(child);
+
+        let clone = world.spawn_empty().id();
+        CloneSubgraph::new(root, clone).write(&mut world);
+
+        // Hierarchy edges leaving the subgraph aren't kept, so neither are `Parent` and
+        // `Children`.
+        assert!(world.transitive_targets::<ChildOf>(clone).is_empty());
+        assert!(world.get::<Parent>(clone).is_none());
+        assert!(world.get::<Children>(clone).is_none());
+        assert_eq!(world.get::<Parent>(child).unwrap().get(), root);
+        assert_eq!(&**world.get::<Children>(parent).unwrap(), &[root]);
+
+        world.entity_mut(clone).despawn_recursive();
+        assert!(world.get_entity(child).is_some());
+        assert_eq!(world.transitive_targets::<ChildOf>(root), vec![child]);
+    }
+}
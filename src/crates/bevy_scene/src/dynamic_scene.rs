@@ -7,8 +7,10 @@ use bevy_ecs::{
     entity::EntityMap,
     prelude::Entity,
     reflect::{ReflectComponent, ReflectMapEntities},
+    relation::ReflectRelation,
     world::World,
 };
+use bevy_hierarchy::link_child_of;
 use bevy_reflect::{Reflect, TypeRegistryArc, TypeUuid};
 use bevy_utils::HashMap;
 
@@ -32,6 +34,7 @@ use serde::Serialize;
 pub struct DynamicScene {
     pub resources: Vec<Box<dyn Reflect>>,
     pub entities: Vec<DynamicEntity>,
+    pub relations: Vec<DynamicRelation>,
 }
 
 /// A reflection-powered serializable representation of an entity and its components.
@@ -43,6 +46,18 @@ pub struct DynamicEntity {
     pub components: Vec<Box<dyn Reflect>>,
 }
 
+/// A reflection-powered serializable representation of a relation edge between two entities of
+/// a [`DynamicScene`].
+pub struct DynamicRelation {
+    /// The transiently unique identifier of the foster the edge starts at.
+    pub foster: u32,
+    /// The transiently unique identifier of the target the edge points to.
+    pub target: u32,
+    /// The value of the edge, a [`Relation`](bevy_ecs::relation::Relation) that implements the
+    /// `Reflect` trait.
+    pub relation: Box<dyn Reflect>,
+}
+
 impl DynamicScene {
     /// Create a new dynamic scene from a given scene.
     pub fn from_scene(scene: &Scene, type_registry: &AppTypeRegistry) -> Self {
@@ -55,6 +70,7 @@ impl DynamicScene {
             DynamicSceneBuilder::from_world_with_type_registry(world, type_registry.clone());
 
         builder.extract_entities(world.iter_entities().map(|entity| entity.id()));
+        builder.extract_relations();
         builder.extract_resources();
 
         builder.build()
@@ -64,7 +80,8 @@ impl DynamicScene {
     ///
     /// This method will return a [`SceneSpawnError`] if a type either is not registered
     /// in the provided [`AppTypeRegistry`] resource, or doesn't reflect the
-    /// [`Component`](bevy_ecs::component::Component) or [`Resource`](bevy_ecs::prelude::Resource) trait.
+    /// [`Component`](bevy_ecs::component::Component), [`Resource`](bevy_ecs::prelude::Resource)
+    /// or [`Relation`](bevy_ecs::relation::Relation) trait.
     pub fn write_to_world_with(
         &self,
         world: &mut World,
@@ -147,6 +164,37 @@ impl DynamicScene {
             }
         }
 
+        // Relation edges are set last so both of their entities exist in the world.
+        for scene_relation in &self.relations {
+            let registration = type_registry
+                .get_with_name(scene_relation.relation.type_name())
+                .ok_or_else(|| SceneSpawnError::UnregisteredType {
+                    type_name: scene_relation.relation.type_name().to_string(),
+                })?;
+            let reflect_relation = registration.data::<ReflectRelation>().ok_or_else(|| {
+                SceneSpawnError::UnregisteredRelation {
+                    type_name: scene_relation.relation.type_name().to_string(),
+                }
+            })?;
+
+            let (Ok(foster), Ok(target)) = (
+                entity_map.get(Entity::from_raw(scene_relation.foster)),
+                entity_map.get(Entity::from_raw(scene_relation.target)),
+            ) else {
+                continue;
+            };
+
+            reflect_relation.set(world, foster, target, &*scene_relation.relation);
+        }
+
+        // Scenes saved without `ChildOf` edges only hold `Parent` and `Children`.
+        let entities = self
+            .entities
+            .iter()
+            .filter_map(|scene_entity| entity_map.get(Entity::from_raw(scene_entity.entity)).ok())
+            .collect::<Vec<_>>();
+        link_child_of(world, entities);
+
         Ok(())
     }
 
@@ -188,9 +236,82 @@ where
 mod tests {
     use bevy_app::AppTypeRegistry;
     use bevy_ecs::{entity::EntityMap, system::Command, world::World};
-    use bevy_hierarchy::{AddChild, Parent};
+    use bevy_hierarchy::{AddChild, ChildOf, Children, DespawnRecursiveExt, Parent};
+
+    use crate::{dynamic_scene_builder::DynamicSceneBuilder, DynamicScene, Scene};
+
+    #[test]
+    fn spawned_hierarchies_get_child_of_edges() {
+        let mut world = World::new();
+        world.init_resource::<AppTypeRegistry>();
+        {
+            let type_registry = world.resource::<AppTypeRegistry>();
+            let mut type_registry = type_registry.write();
+            type_registry.register::<Parent>();
+            type_registry.register::<Children>();
+        }
+        let parent = world.spawn_empty().id();
+        let child = world.spawn_empty().id();
+        AddChild { parent, child }.write(&mut world);
 
-    use crate::dynamic_scene_builder::DynamicSceneBuilder;
+        // `ChildOf` isn't registered so the scene only holds `Parent` and `Children`.
+        let mut scene_builder = DynamicSceneBuilder::from_world(&world);
+        scene_builder.extract_entities([parent, child].into_iter());
+        let dynamic_scene = scene_builder.build();
+        let mut entity_map = EntityMap::default();
+        dynamic_scene
+            .write_to_world(&mut world, &mut entity_map)
+            .unwrap();
+
+        let scene_parent = entity_map.get(parent).unwrap();
+        let scene_child = entity_map.get(child).unwrap();
+        assert!(world.is_reachable::<ChildOf>(scene_parent, scene_child));
+
+        // Scene worlds hold edges, which are recreated instead of copied.
+        let type_registry = world.resource::<AppTypeRegistry>().clone();
+        let scene = Scene::from_dynamic_scene(&dynamic_scene, &type_registry).unwrap();
+        let instance_info = scene
+            .write_to_world_with(&mut world, &type_registry)
+            .unwrap();
+
+        let instance_parent = instance_info
+            .entity_map
+            .values()
+            .find(|entity| world.get::<Children>(*entity).is_some())
+            .unwrap();
+        let instance_child = world.get::<Children>(instance_parent).unwrap()[0];
+        assert!(world.is_reachable::<ChildOf>(instance_parent, instance_child));
+
+        world.entity_mut(instance_parent).despawn_recursive();
+        assert!(world.get_entity(instance_child).is_none());
+        assert!(world.get_entity(scene_child).is_some());
+    }
+
+    #[test]
+    fn from_world_extracts_relations() {
+        let mut world = World::new();
+        world.init_resource::<AppTypeRegistry>();
+        world
+            .resource_mut::<AppTypeRegistry>()
+            .write()
+            .register::<ChildOf>();
+        let parent = world.spawn_empty().id();
+        let child = world.spawn_empty().id();
+        AddChild { parent, child }.write(&mut world);
+
+        let type_registry = world.resource::<AppTypeRegistry>().clone();
+        let dynamic_scene = DynamicScene::from_world(&world, &type_registry);
+        assert_eq!(dynamic_scene.relations.len(), 1);
+
+        let mut entity_map = EntityMap::default();
+        dynamic_scene
+            .write_to_world(&mut world, &mut entity_map)
+            .unwrap();
+        assert!(world.is_reachable::<ChildOf>(
+            entity_map.get(parent).unwrap(),
+            entity_map.get(child).unwrap()
+        ));
+    }
 
     #[test]
     fn components_not_defined_in_scene_should_not_be_affected_by_scene_entity_map() {
@@ -1,13 +1,14 @@
-use crate::{DynamicEntity, DynamicScene};
+use crate::{DynamicEntity, DynamicRelation, DynamicScene};
 use bevy_app::AppTypeRegistry;
 use bevy_ecs::component::ComponentId;
 use bevy_ecs::{
     prelude::Entity,
     reflect::{ReflectComponent, ReflectResource},
+    relation::ReflectRelation,
     world::World,
 };
 use bevy_reflect::Reflect;
-use bevy_utils::default;
+use bevy_utils::{default, HashSet};
 use std::collections::BTreeMap;
 
 /// A [`DynamicScene`] builder, used to build a scene from a [`World`] by extracting some entities and resources.
@@ -39,6 +40,7 @@ use std::collections::BTreeMap;
 pub struct DynamicSceneBuilder<'w> {
     extracted_resources: BTreeMap<ComponentId, Box<dyn Reflect>>,
     extracted_scene: BTreeMap<u32, DynamicEntity>,
+    extracted_relations: BTreeMap<(u32, String), Vec<DynamicRelation>>,
     type_registry: AppTypeRegistry,
     original_world: &'w World,
 }
@@ -50,6 +52,7 @@ impl<'w> DynamicSceneBuilder<'w> {
         Self {
             extracted_resources: default(),
             extracted_scene: default(),
+            extracted_relations: default(),
             type_registry: world.resource::<AppTypeRegistry>().clone(),
             original_world: world,
         }
@@ -61,6 +64,7 @@ impl<'w> DynamicSceneBuilder<'w> {
         Self {
             extracted_resources: default(),
             extracted_scene: default(),
+            extracted_relations: default(),
             type_registry,
             original_world: world,
         }
@@ -74,6 +78,7 @@ impl<'w> DynamicSceneBuilder<'w> {
         DynamicScene {
             resources: self.extracted_resources.into_values().collect(),
             entities: self.extracted_scene.into_values().collect(),
+            relations: self.extracted_relations.into_values().flatten().collect(),
         }
     }
 
@@ -91,6 +96,15 @@ impl<'w> DynamicSceneBuilder<'w> {
         self.extracted_scene
             .retain(|_, entity| !entity.components.is_empty());
 
+        let extracted_scene = &self.extracted_scene;
+        self.extracted_relations.retain(|_, relations| {
+            relations.retain(|relation| {
+                extracted_scene.contains_key(&relation.foster)
+                    && extracted_scene.contains_key(&relation.target)
+            });
+            !relations.is_empty()
+        });
+
         self
     }
 
@@ -158,6 +172,145 @@ impl<'w> DynamicSceneBuilder<'w> {
         self
     }
 
+    /// Extract `root` and every entity reachable from it through at most `depth` edges of any
+    /// of the given `relations`, together with the edges of those relations among them.
+    ///
+    /// Paths may mix relations, so passing the [`ReflectRelation`]s of
+    /// [`ChildOf`](bevy_hierarchy::ChildOf) and another relation also reaches entities related to
+    /// the children and children of related entities.
+    /// Edges to entities that weren't reached are left out so the scene is self-contained.
+    /// Writing the scene back requires the relations to be registered with `#[reflect(Relation)]`.
+    ///
+    /// Re-extracting an entity or an edge that was already extracted will have no effect.
+    /// ```
+    /// # use bevy_scene::DynamicSceneBuilder;
+    /// # use bevy_app::AppTypeRegistry;
+    /// # use bevy_ecs::{
+    /// #     component::TableStorage, relation::{ReflectRelation, Relation}, world::World,
+    /// # };
+    /// # use bevy_reflect::{FromType, Reflect};
+    /// #[derive(Reflect, Default)]
+    /// #[reflect(Relation)]
+    /// struct MountedOn;
+    ///
+    /// impl Relation for MountedOn {
+    ///     type Storage = TableStorage;
+    /// }
+    ///
+    /// # let mut world = World::default();
+    /// # world.init_resource::<AppTypeRegistry>();
+    /// let vehicle = world.spawn_empty().id();
+    /// let turret = world.spawn_empty().id();
+    /// world.set_relations_batch([(vehicle, turret, MountedOn)]);
+    ///
+    /// let mounted_on = <ReflectRelation as FromType<MountedOn>>::from_type();
+    /// let mut builder = DynamicSceneBuilder::from_world(&world);
+    /// builder.extract_related(vehicle, &[mounted_on], usize::MAX);
+    /// let scene = builder.build();
+    /// assert_eq!(scene.relations.len(), 1);
+    /// ```
+    pub fn extract_related(
+        &mut self,
+        root: Entity,
+        relations: &[ReflectRelation],
+        depth: usize,
+    ) -> &mut Self {
+        let mut reached = vec![root];
+        let mut visited = HashSet::from_iter([root]);
+        let mut frontier = vec![root];
+
+        for _ in 0..depth {
+            if frontier.is_empty() {
+                break;
+            }
+
+            let mut next = Vec::new();
+            for foster in frontier {
+                for reflect_relation in relations {
+                    for (target, _) in reflect_relation.targets(self.original_world, foster) {
+                        if visited.insert(target) {
+                            next.push(target);
+                        }
+                    }
+                }
+            }
+
+            reached.extend(next.iter().copied());
+            frontier = next;
+        }
+
+        self.extract_entities(reached.iter().copied());
+
+        for foster in reached {
+            for reflect_relation in relations {
+                self.extract_edges(reflect_relation, foster, |target| visited.contains(&target));
+            }
+        }
+
+        self
+    }
+
+    /// Extract the edges between extracted entities of every relation registered with
+    /// `#[reflect(Relation)]` in the builder's [`AppTypeRegistry`].
+    ///
+    /// Only entities extracted before this call are considered.
+    /// Re-extracting an edge that was already extracted will have no effect.
+    pub fn extract_relations(&mut self) -> &mut Self {
+        let type_registry = self.type_registry.clone();
+        let type_registry = type_registry.read();
+
+        let extracted = self
+            .extracted_scene
+            .keys()
+            .filter_map(|index| self.original_world.entities().resolve_from_id(*index))
+            .collect::<HashSet<_>>();
+
+        for registration in type_registry.iter() {
+            let Some(reflect_relation) = registration.data::<ReflectRelation>() else {
+                continue;
+            };
+
+            for foster in &extracted {
+                self.extract_edges(reflect_relation, *foster, |target| {
+                    extracted.contains(&target)
+                });
+            }
+        }
+
+        self
+    }
+
+    // Pushes the edges from `foster` to the targets matching `include` unless they were already
+    // extracted, keeping the order of the targets.
+    fn extract_edges(
+        &mut self,
+        reflect_relation: &ReflectRelation,
+        foster: Entity,
+        include: impl Fn(Entity) -> bool,
+    ) {
+        for (target, relation) in reflect_relation.targets(self.original_world, foster) {
+            if !include(target) {
+                continue;
+            }
+
+            let relations = self
+                .extracted_relations
+                .entry((foster.index(), relation.type_name().to_string()))
+                .or_default();
+
+            if !relations
+                .iter()
+                .any(|extracted| extracted.target == target.index())
+            {
+                relations.push(DynamicRelation {
+                    foster: foster.index(),
+                    target: target.index(),
+                    relation: relation.clone_value(),
+                });
+            }
+        }
+    }
+
     /// Extract resources from the builder's [`World`].
     ///
     /// Only resources registered in the builder's [`AppTypeRegistry`] will be extracted.
@@ -208,11 +361,13 @@ impl<'w> DynamicSceneBuilder<'w> {
 mod tests {
     use bevy_app::AppTypeRegistry;
     use bevy_ecs::{
-        component::Component, prelude::Entity, prelude::Resource, query::With,
-        reflect::ReflectComponent, reflect::ReflectResource, world::World,
+        component::Component, component::TableStorage, prelude::Entity, prelude::Resource,
+        query::With, reflect::ReflectComponent, reflect::ReflectResource,
+        relation::ReflectRelation, relation::Relation, world::World,
     };
 
-    use bevy_reflect::Reflect;
+    use bevy_hierarchy::ChildOf;
+    use bevy_reflect::{FromReflect, FromType, Reflect};
 
     use super::DynamicSceneBuilder;
 
@@ -228,6 +383,14 @@ mod tests {
     #[reflect(Resource)]
     struct ResourceA;
 
+    #[derive(Reflect, FromReflect, Default, Eq, PartialEq, Debug)]
+    #[reflect(Relation)]
+    struct MountedOn(u32);
+
+    impl Relation for MountedOn {
+        type Storage = TableStorage;
+    }
+
     #[test]
     fn extract_one_entity() {
         let mut world = World::default();
@@ -404,4 +567,111 @@ mod tests {
         assert_eq!(scene.resources.len(), 1);
         assert!(scene.resources[0].represents::<ResourceA>());
     }
+
+    #[test]
+    fn extract_related() {
+        let mut world = World::default();
+
+        let atr = AppTypeRegistry::default();
+        atr.write().register::<ComponentA>();
+        world.insert_resource(atr);
+
+        let [vehicle, turret, barrel, scope, other] = [(); 5].map(|_| world.spawn(ComponentA).id());
+        world.set_relations_batch([
+            (vehicle, turret, MountedOn(0)),
+            (turret, barrel, MountedOn(1)),
+            (barrel, scope, MountedOn(2)),
+            (other, turret, MountedOn(3)),
+        ]);
+
+        let mounted_on = [<ReflectRelation as FromType<MountedOn>>::from_type()];
+        let mut builder = DynamicSceneBuilder::from_world(&world);
+        builder.extract_related(vehicle, &mounted_on, 2);
+        builder.extract_related(turret, &mounted_on, 1);
+        let scene = builder.build();
+
+        let mut entities = scene
+            .entities
+            .iter()
+            .map(|entity| entity.entity)
+            .collect::<Vec<_>>();
+        entities.sort();
+        assert_eq!(
+            entities,
+            vec![vehicle.index(), turret.index(), barrel.index()]
+        );
+
+        // Only edges between extracted entities are kept, and only once.
+        let mut relations = scene
+            .relations
+            .iter()
+            .map(|relation| {
+                (
+                    relation.foster,
+                    relation.target,
+                    MountedOn::from_reflect(&*relation.relation).unwrap().0,
+                )
+            })
+            .collect::<Vec<_>>();
+        relations.sort();
+        assert_eq!(
+            relations,
+            vec![
+                (vehicle.index(), turret.index(), 0),
+                (turret.index(), barrel.index(), 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn extract_related_through_mixed_paths() {
+        let mut world = World::default();
+
+        let atr = AppTypeRegistry::default();
+        atr.write().register::<ComponentA>();
+        world.insert_resource(atr);
+
+        // The turret is a child of the vehicle and the gun is mounted on the turret.
+        let [vehicle, turret, gun, other] = [(); 4].map(|_| world.spawn(ComponentA).id());
+        world.set_relations_batch([(vehicle, turret, ChildOf)]);
+        world.set_relations_batch([(turret, gun, MountedOn(0)), (other, gun, MountedOn(1))]);
+
+        let child_of = <ReflectRelation as FromType<ChildOf>>::from_type();
+        let mounted_on = <ReflectRelation as FromType<MountedOn>>::from_type();
+        let mut builder = DynamicSceneBuilder::from_world(&world);
+        builder.extract_related(vehicle, &[child_of, mounted_on], usize::MAX);
+        let scene = builder.build();
+
+        let mut entities = scene
+            .entities
+            .iter()
+            .map(|entity| entity.entity)
+            .collect::<Vec<_>>();
+        entities.sort();
+        assert_eq!(entities, vec![vehicle.index(), turret.index(), gun.index()]);
+        assert_eq!(scene.relations.len(), 2);
+    }
+
+    #[test]
+    fn extract_relations() {
+        let mut world = World::default();
+
+        let atr = AppTypeRegistry::default();
+        atr.write().register::<ComponentA>();
+        atr.write().register::<MountedOn>();
+        world.insert_resource(atr);
+
+        let [a, b, c] = [(); 3].map(|_| world.spawn(ComponentA).id());
+        world.set_relations_batch([(a, b, MountedOn(0)), (b, c, MountedOn(1))]);
+
+        let mut builder = DynamicSceneBuilder::from_world(&world);
+        builder.extract_entities([a, b].into_iter());
+        builder.extract_relations();
+        builder.extract_relations();
+        let scene = builder.build();
+
+        assert_eq!(scene.relations.len(), 1);
+        assert_eq!(scene.relations[0].foster, a.index());
+        assert_eq!(scene.relations[0].target, b.index());
+    }
 }
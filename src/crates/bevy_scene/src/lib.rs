@@ -1,4 +1,5 @@
 mod bundle;
+mod clone_subgraph;
 mod dynamic_scene;
 mod dynamic_scene_builder;
 mod scene;
@@ -9,6 +10,7 @@ mod scene_spawner;
 pub mod serde;
 
 pub use bundle::*;
+pub use clone_subgraph::*;
 pub use dynamic_scene::*;
 pub use dynamic_scene_builder::*;
 pub use scene::*;
@@ -2,8 +2,10 @@ use bevy_app::AppTypeRegistry;
 use bevy_ecs::{
     entity::EntityMap,
     reflect::{ReflectComponent, ReflectMapEntities, ReflectResource},
+    relation::ReflectRelation,
     world::World,
 };
+use bevy_hierarchy::link_child_of;
 use bevy_reflect::TypeUuid;
 
 use crate::{DynamicScene, InstanceInfo, SceneSpawnError};
@@ -47,10 +49,16 @@ impl Scene {
         Ok(Self { world: new_world })
     }
 
-    /// Write the entities and their corresponding components to the given world.
+    /// Write the entities, their corresponding components and the edges of their relations to the
+    /// given world.
     ///
     /// This method will return a [`SceneSpawnError`] if a type either is not registered in the
     /// provided [`AppTypeRegistry`] or doesn't reflect the [`Component`](bevy_ecs::component::Component) trait.
+    ///
+    /// Unlike [`DynamicScene::write_to_world_with`], edges of relations that aren't registered
+    /// with `#[reflect(Relation)]` are dropped instead of returning
+    /// [`SceneSpawnError::UnregisteredRelation`], since the scene world only knows the type of
+    /// their storage.
     pub fn write_to_world_with(
         &self,
         world: &mut World,
@@ -64,6 +72,10 @@ impl Scene {
 
         // Resources archetype
         for (component_id, _) in self.world.storages().resources.iter() {
+            if self.world.is_relation_data(component_id) {
+                continue;
+            }
+
             let component_info = self
                 .world
                 .components()
@@ -88,13 +100,21 @@ impl Scene {
             reflect_resource.copy(&self.world, world);
         }
 
+        let mut written = Vec::new();
+
         for archetype in self.world.archetypes().iter() {
             for scene_entity in archetype.entities() {
                 let entity = *instance_info
                     .entity_map
                     .entry(scene_entity.entity())
                     .or_insert_with(|| world.spawn_empty().id());
+                written.push((scene_entity.entity(), entity));
                 for component_id in archetype.components() {
+                    // Edges are recreated below, once every entity exists.
+                    if self.world.is_relation_data(component_id) {
+                        continue;
+                    }
+
                     let component_info = self
                         .world
                         .components()
@@ -126,6 +146,23 @@ impl Scene {
             }
         }
 
+        // Only edges of entities written by this call are set, the entity map may hold others.
+        for reflect_relation in type_registry
+            .iter()
+            .filter_map(|registration| registration.data::<ReflectRelation>())
+        {
+            for (scene_entity, entity) in written.iter().copied() {
+                for (target, relation) in reflect_relation.targets(&self.world, scene_entity) {
+                    if let Ok(target) = instance_info.entity_map.get(target) {
+                        reflect_relation.set(world, entity, target, relation);
+                    }
+                }
+            }
+        }
+
+        // Scenes built without `ChildOf` edges only hold `Parent` and `Children`.
+        link_child_of(world, written.into_iter().map(|(_, entity)| entity));
+
         Ok(instance_info)
     }
 }
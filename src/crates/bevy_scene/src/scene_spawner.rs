@@ -47,6 +47,8 @@ pub enum SceneSpawnError {
     UnregisteredComponent { type_name: String },
     #[error("scene contains the unregistered resource `{type_name}`. consider adding `#[reflect(Resource)]` to your type")]
     UnregisteredResource { type_name: String },
+    #[error("scene contains the unregistered relation `{type_name}`. consider adding `#[reflect(Relation)]` to your type")]
+    UnregisteredRelation { type_name: String },
     #[error("scene contains the unregistered type `{type_name}`. consider registering the type using `app.register_type::<T>()`")]
     UnregisteredType { type_name: String },
     #[error("scene does not exist")]
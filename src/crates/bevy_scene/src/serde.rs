@@ -1,4 +1,4 @@
-use crate::{DynamicEntity, DynamicScene};
+use crate::{DynamicEntity, DynamicRelation, DynamicScene};
 use anyhow::Result;
 use bevy_reflect::serde::{TypedReflectDeserializer, TypedReflectSerializer};
 use bevy_reflect::{
@@ -6,7 +6,7 @@ use bevy_reflect::{
     Reflect, TypeRegistry, TypeRegistryArc,
 };
 use bevy_utils::HashSet;
-use serde::ser::SerializeMap;
+use serde::ser::{SerializeMap, SerializeSeq};
 use serde::{
     de::{DeserializeSeed, Error, MapAccess, SeqAccess, Visitor},
     ser::SerializeStruct,
@@ -17,10 +17,16 @@ use std::fmt::Formatter;
 pub const SCENE_STRUCT: &str = "Scene";
 pub const SCENE_RESOURCES: &str = "resources";
 pub const SCENE_ENTITIES: &str = "entities";
+pub const SCENE_RELATIONS: &str = "relations";
 
 pub const ENTITY_STRUCT: &str = "Entity";
 pub const ENTITY_FIELD_COMPONENTS: &str = "components";
 
+pub const RELATION_STRUCT: &str = "Relation";
+pub const RELATION_FIELD_FOSTER: &str = "foster";
+pub const RELATION_FIELD_TARGET: &str = "target";
+pub const RELATION_FIELD_RELATION: &str = "relation";
+
 pub struct SceneSerializer<'a> {
     pub scene: &'a DynamicScene,
     pub registry: &'a TypeRegistryArc,
@@ -37,7 +43,7 @@ impl<'a> Serialize for SceneSerializer<'a> {
     where
         S: serde::Serializer,
     {
-        let mut state = serializer.serialize_struct(SCENE_STRUCT, 2)?;
+        let mut state = serializer.serialize_struct(SCENE_STRUCT, 3)?;
         state.serialize_field(
             SCENE_RESOURCES,
             &SceneMapSerializer {
@@ -52,6 +58,13 @@ impl<'a> Serialize for SceneSerializer<'a> {
                 registry: self.registry,
             },
         )?;
+        state.serialize_field(
+            SCENE_RELATIONS,
+            &RelationsSerializer {
+                relations: &self.scene.relations,
+                registry: self.registry,
+            },
+        )?;
         state.end()
     }
 }
@@ -102,6 +115,52 @@ impl<'a> Serialize for EntitySerializer<'a> {
     }
 }
 
+pub struct RelationsSerializer<'a> {
+    pub relations: &'a [DynamicRelation],
+    pub registry: &'a TypeRegistryArc,
+}
+
+impl<'a> Serialize for RelationsSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_seq(Some(self.relations.len()))?;
+        for relation in self.relations {
+            state.serialize_element(&RelationSerializer {
+                relation,
+                registry: self.registry,
+            })?;
+        }
+        state.end()
+    }
+}
+
+pub struct RelationSerializer<'a> {
+    pub relation: &'a DynamicRelation,
+    pub registry: &'a TypeRegistryArc,
+}
+
+impl<'a> Serialize for RelationSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct(RELATION_STRUCT, 3)?;
+        state.serialize_field(RELATION_FIELD_FOSTER, &self.relation.foster)?;
+        state.serialize_field(RELATION_FIELD_TARGET, &self.relation.target)?;
+        // Stored as a single entry map keyed by the type name, like components.
+        state.serialize_field(
+            RELATION_FIELD_RELATION,
+            &SceneMapSerializer {
+                entries: std::slice::from_ref(&self.relation.relation),
+                registry: self.registry,
+            },
+        )?;
+        state.end()
+    }
+}
+
 pub struct SceneMapSerializer<'a> {
     pub entries: &'a [Box<dyn Reflect>],
     pub registry: &'a TypeRegistryArc,
@@ -128,6 +187,7 @@ impl<'a> Serialize for SceneMapSerializer<'a> {
 enum SceneField {
     Resources,
     Entities,
+    Relations,
 }
 
 #[derive(Deserialize)]
@@ -136,6 +196,14 @@ enum EntityField {
     Components,
 }
 
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "lowercase")]
+enum RelationField {
+    Foster,
+    Target,
+    Relation,
+}
+
 pub struct SceneDeserializer<'a> {
     pub type_registry: &'a TypeRegistry,
 }
@@ -149,7 +217,7 @@ impl<'a, 'de> DeserializeSeed<'de> for SceneDeserializer<'a> {
     {
         deserializer.deserialize_struct(
             SCENE_STRUCT,
-            &[SCENE_RESOURCES, SCENE_ENTITIES],
+            &[SCENE_RESOURCES, SCENE_ENTITIES, SCENE_RELATIONS],
             SceneVisitor {
                 type_registry: self.type_registry,
             },
@@ -174,6 +242,7 @@ impl<'a, 'de> Visitor<'de> for SceneVisitor<'a> {
     {
         let mut resources = None;
         let mut entities = None;
+        let mut relations = None;
         while let Some(key) = map.next_key()? {
             match key {
                 SceneField::Resources => {
@@ -192,15 +261,26 @@ impl<'a, 'de> Visitor<'de> for SceneVisitor<'a> {
                         type_registry: self.type_registry,
                     })?);
                 }
+                SceneField::Relations => {
+                    if relations.is_some() {
+                        return Err(Error::duplicate_field(SCENE_RELATIONS));
+                    }
+                    relations = Some(map.next_value_seed(SceneRelationsDeserializer {
+                        type_registry: self.type_registry,
+                    })?);
+                }
             }
         }
 
         let resources = resources.ok_or_else(|| Error::missing_field(SCENE_RESOURCES))?;
         let entities = entities.ok_or_else(|| Error::missing_field(SCENE_ENTITIES))?;
+        // Scenes saved before relations were supported don't have this field.
+        let relations = relations.unwrap_or_default();
 
         Ok(DynamicScene {
             resources,
             entities,
+            relations,
         })
     }
 
@@ -220,9 +300,16 @@ impl<'a, 'de> Visitor<'de> for SceneVisitor<'a> {
             })?
             .ok_or_else(|| Error::missing_field(SCENE_ENTITIES))?;
 
+        let relations = seq
+            .next_element_seed(SceneRelationsDeserializer {
+                type_registry: self.type_registry,
+            })?
+            .unwrap_or_default();
+
         Ok(DynamicScene {
             resources,
             entities,
+            relations,
         })
     }
 }
@@ -352,6 +439,163 @@ impl<'a, 'de> Visitor<'de> for SceneEntityVisitor<'a> {
     }
 }
 
+pub struct SceneRelationsDeserializer<'a> {
+    pub type_registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for SceneRelationsDeserializer<'a> {
+    type Value = Vec<DynamicRelation>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(SceneRelationsVisitor {
+            type_registry: self.type_registry,
+        })
+    }
+}
+
+struct SceneRelationsVisitor<'a> {
+    pub type_registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for SceneRelationsVisitor<'a> {
+    type Value = Vec<DynamicRelation>;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("sequence of relations")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut relations = Vec::new();
+        while let Some(relation) = seq.next_element_seed(SceneRelationDeserializer {
+            type_registry: self.type_registry,
+        })? {
+            relations.push(relation);
+        }
+
+        Ok(relations)
+    }
+}
+
+pub struct SceneRelationDeserializer<'a> {
+    pub type_registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for SceneRelationDeserializer<'a> {
+    type Value = DynamicRelation;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_struct(
+            RELATION_STRUCT,
+            &[
+                RELATION_FIELD_FOSTER,
+                RELATION_FIELD_TARGET,
+                RELATION_FIELD_RELATION,
+            ],
+            SceneRelationVisitor {
+                registry: self.type_registry,
+            },
+        )
+    }
+}
+
+struct SceneRelationVisitor<'a> {
+    pub registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for SceneRelationVisitor<'a> {
+    type Value = DynamicRelation;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("relation struct")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> std::result::Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let foster = seq
+            .next_element()?
+            .ok_or_else(|| Error::missing_field(RELATION_FIELD_FOSTER))?;
+        let target = seq
+            .next_element()?
+            .ok_or_else(|| Error::missing_field(RELATION_FIELD_TARGET))?;
+        let relation = seq
+            .next_element_seed(SceneMapDeserializer {
+                registry: self.registry,
+            })?
+            .ok_or_else(|| Error::missing_field(RELATION_FIELD_RELATION))?;
+
+        Ok(DynamicRelation {
+            foster,
+            target,
+            relation: single_relation(relation)?,
+        })
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut foster = None;
+        let mut target = None;
+        let mut relation = None;
+        while let Some(key) = map.next_key()? {
+            match key {
+                RelationField::Foster => {
+                    if foster.is_some() {
+                        return Err(Error::duplicate_field(RELATION_FIELD_FOSTER));
+                    }
+                    foster = Some(map.next_value()?);
+                }
+                RelationField::Target => {
+                    if target.is_some() {
+                        return Err(Error::duplicate_field(RELATION_FIELD_TARGET));
+                    }
+                    target = Some(map.next_value()?);
+                }
+                RelationField::Relation => {
+                    if relation.is_some() {
+                        return Err(Error::duplicate_field(RELATION_FIELD_RELATION));
+                    }
+                    relation = Some(map.next_value_seed(SceneMapDeserializer {
+                        registry: self.registry,
+                    })?);
+                }
+            }
+        }
+
+        let foster = foster.ok_or_else(|| Error::missing_field(RELATION_FIELD_FOSTER))?;
+        let target = target.ok_or_else(|| Error::missing_field(RELATION_FIELD_TARGET))?;
+        let relation = relation.ok_or_else(|| Error::missing_field(RELATION_FIELD_RELATION))?;
+
+        Ok(DynamicRelation {
+            foster,
+            target,
+            relation: single_relation(relation)?,
+        })
+    }
+}
+
+fn single_relation<E: Error>(mut relation: Vec<Box<dyn Reflect>>) -> Result<Box<dyn Reflect>, E> {
+    if relation.len() != 1 {
+        return Err(Error::invalid_length(
+            relation.len(),
+            &"a map with exactly one relation",
+        ));
+    }
+
+    Ok(relation.remove(0))
+}
+
 pub struct SceneMapDeserializer<'a> {
     pub registry: &'a TypeRegistry,
 }
@@ -426,7 +670,11 @@ mod tests {
     use bevy_app::AppTypeRegistry;
     use bevy_ecs::entity::EntityMap;
     use bevy_ecs::prelude::{Component, ReflectComponent, ReflectResource, Resource, World};
-    use bevy_reflect::{FromReflect, Reflect, ReflectSerialize};
+    use bevy_ecs::{
+        component::TableStorage,
+        relation::{ReflectRelation, Relation},
+    };
+    use bevy_reflect::{FromReflect, FromType, Reflect, ReflectSerialize};
     use bincode::Options;
     use serde::de::DeserializeSeed;
     use serde::Serialize;
@@ -466,6 +714,14 @@ mod tests {
         foo: i32,
     }
 
+    #[derive(Reflect, Default)]
+    #[reflect(Relation)]
+    struct MountedOn(i32);
+
+    impl Relation for MountedOn {
+        type Storage = TableStorage;
+    }
+
     fn create_world() -> World {
         let mut world = World::new();
         let registry = AppTypeRegistry::default();
@@ -481,6 +737,7 @@ mod tests {
             registry.register::<[usize; 3]>();
             registry.register::<(f32, f32)>();
             registry.register::<MyResource>();
+            registry.register::<MountedOn>();
         }
         world.insert_resource(registry);
         world
@@ -527,6 +784,7 @@ mod tests {
       },
     ),
   },
+  relations: [],
 )"#;
         let output = scene
             .serialize_ron(&world.resource::<AppTypeRegistry>().0)
@@ -596,6 +854,43 @@ mod tests {
         assert_eq!(1, dst_world.query::<&Baz>().iter(&dst_world).count());
     }
 
+    #[test]
+    fn should_roundtrip_relations() {
+        let mut world = create_world();
+
+        let vehicle = world.spawn(Foo(1)).id();
+        let turret = world.spawn(Foo(2)).id();
+        world.set_relations_batch([(vehicle, turret, MountedOn(7))]);
+
+        let mounted_on = <ReflectRelation as FromType<MountedOn>>::from_type();
+        let mut builder = DynamicSceneBuilder::from_world(&world);
+        builder.extract_related(vehicle, &[mounted_on], usize::MAX);
+        let scene = builder.build();
+
+        let registry = world.resource::<AppTypeRegistry>();
+        let serialized_scene = scene.serialize_ron(&registry.0).unwrap();
+
+        let mut deserializer = ron::de::Deserializer::from_str(&serialized_scene).unwrap();
+        let scene_deserializer = SceneDeserializer {
+            type_registry: &registry.read(),
+        };
+        let deserialized_scene = scene_deserializer.deserialize(&mut deserializer).unwrap();
+        assert_eq!(1, deserialized_scene.relations.len());
+
+        let mut map = EntityMap::default();
+        let mut dst_world = create_world();
+        deserialized_scene
+            .write_to_world(&mut dst_world, &mut map)
+            .unwrap();
+
+        let vehicle = map.get(vehicle).unwrap();
+        let turret = map.get(turret).unwrap();
+        assert_eq!(
+            dst_world.transitive_targets::<MountedOn>(vehicle),
+            vec![turret]
+        );
+    }
+
     #[test]
     fn should_roundtrip_postcard() {
         let mut world = create_world();
@@ -618,7 +913,7 @@ mod tests {
                 0, 1, 0, 1, 37, 98, 101, 118, 121, 95, 115, 99, 101, 110, 101, 58, 58, 115, 101,
                 114, 100, 101, 58, 58, 116, 101, 115, 116, 115, 58, 58, 77, 121, 67, 111, 109, 112,
                 111, 110, 101, 110, 116, 1, 2, 3, 102, 102, 166, 63, 205, 204, 108, 64, 1, 12, 72,
-                101, 108, 108, 111, 32, 87, 111, 114, 108, 100, 33
+                101, 108, 108, 111, 32, 87, 111, 114, 108, 100, 33, 0
             ],
             serialized_scene
         );
@@ -655,11 +950,11 @@ mod tests {
 
         assert_eq!(
             vec![
-                146, 128, 129, 0, 145, 129, 217, 37, 98, 101, 118, 121, 95, 115, 99, 101, 110, 101,
+                147, 128, 129, 0, 145, 129, 217, 37, 98, 101, 118, 121, 95, 115, 99, 101, 110, 101,
                 58, 58, 115, 101, 114, 100, 101, 58, 58, 116, 101, 115, 116, 115, 58, 58, 77, 121,
                 67, 111, 109, 112, 111, 110, 101, 110, 116, 147, 147, 1, 2, 3, 146, 202, 63, 166,
                 102, 102, 202, 64, 108, 204, 205, 129, 165, 84, 117, 112, 108, 101, 172, 72, 101,
-                108, 108, 111, 32, 87, 111, 114, 108, 100, 33
+                108, 108, 111, 32, 87, 111, 114, 108, 100, 33, 144
             ],
             buf
         );
@@ -701,7 +996,8 @@ mod tests {
                 115, 101, 114, 100, 101, 58, 58, 116, 101, 115, 116, 115, 58, 58, 77, 121, 67, 111,
                 109, 112, 111, 110, 101, 110, 116, 1, 0, 0, 0, 0, 0, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0,
                 3, 0, 0, 0, 0, 0, 0, 0, 102, 102, 166, 63, 205, 204, 108, 64, 1, 0, 0, 0, 12, 0, 0,
-                0, 0, 0, 0, 0, 72, 101, 108, 108, 111, 32, 87, 111, 114, 108, 100, 33
+                0, 0, 0, 0, 0, 72, 101, 108, 108, 111, 32, 87, 111, 114, 108, 100, 33, 0, 0, 0, 0,
+                0, 0, 0, 0
             ],
             serialized_scene
         );
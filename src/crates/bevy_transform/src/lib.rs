@@ -13,14 +13,16 @@ pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
         commands::BuildChildrenTransformExt, components::*, TransformBundle, TransformPlugin,
+        TransformPropagationPlugin,
     };
 }
 
 use bevy_app::prelude::*;
-use bevy_ecs::prelude::*;
+use bevy_ecs::{prelude::*, relation::Relation};
 use bevy_hierarchy::ValidParentCheckPlugin;
 use prelude::{GlobalTransform, Transform};
-use systems::{propagate_transforms, sync_simple_transforms};
+use std::marker::PhantomData;
+use systems::{propagate_transforms, propagate_transforms_of, sync_simple_transforms};
 
 /// A [`Bundle`] of the [`Transform`] and [`GlobalTransform`]
 /// [`Component`](bevy_ecs::component::Component)s, which describe the position of an entity.
@@ -130,3 +132,34 @@ impl Plugin for TransformPlugin {
             );
     }
 }
+
+/// Propagates [`Transform`] components from fosters to targets of relation `R`.
+///
+/// Requires [`TransformPlugin`], whose systems keep the [`GlobalTransform`] of the roots up to
+/// date. See [`propagate_transforms_of`] for details.
+pub struct TransformPropagationPlugin<R: Relation>(PhantomData<fn() -> R>);
+
+impl<R: Relation> Default for TransformPropagationPlugin<R> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<R: Relation> Plugin for TransformPropagationPlugin<R> {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PostStartup,
+            propagate_transforms_of::<R>
+                .in_set(TransformSystem::TransformPropagate)
+                .after(sync_simple_transforms)
+                .after(propagate_transforms),
+        )
+        .add_systems(
+            PostUpdate,
+            propagate_transforms_of::<R>
+                .in_set(TransformSystem::TransformPropagate)
+                .after(sync_simple_transforms)
+                .after(propagate_transforms),
+        );
+    }
+}
@@ -2,6 +2,7 @@ use crate::components::{GlobalTransform, Transform};
 use bevy_ecs::{
     change_detection::Ref,
     prelude::{Changed, DetectChanges, Entity, Query, With, Without},
+    relation::{Edges, HasRelation, Relation, RelationGraph},
 };
 use bevy_hierarchy::{Children, Parent};
 
@@ -152,18 +153,145 @@ unsafe fn propagate_recursive(
     }
 }
 
+/// Update [`GlobalTransform`] component of entities based on relation `R` and [`Transform`]
+/// component. Transforms propagate from each foster to its targets.
+///
+/// Roots are fosters of `R` that aren't targeted by `R`. Their [`GlobalTransform`] is read,
+/// not written, so it must be kept up to date by [`sync_simple_transforms`] and
+/// [`propagate_transforms`], which this system should run after. Entities without a [`Parent`]
+/// that stopped being targeted by `R` are reset to their [`Transform`].
+///
+/// # Panics
+///
+/// If an entity reachable from a root is targeted by more than one foster of `R`.
+pub fn propagate_transforms_of<R: Relation>(
+    root_query: Query<Entity, HasRelation<R>>,
+    detached_query: Query<(Entity, &Edges), (Changed<Edges>, Without<Parent>)>,
+    graph: RelationGraph<R>,
+    edges_query: Query<Ref<Edges>>,
+    mut transform_query: Query<(Ref<Transform>, &mut GlobalTransform)>,
+) {
+    // Edges change for every relation, only entities that lost their last `R` foster are reset
+    // so targets of other relations keep their propagated transform.
+    for (entity, edges) in &detached_query {
+        if !edges.lost_fosters::<R>() {
+            continue;
+        }
+        if let Ok((transform, mut global_transform)) = transform_query.get_mut(entity) {
+            *global_transform = GlobalTransform::from(*transform);
+        }
+    }
+
+    root_query.par_iter().for_each(|entity| {
+        if graph.in_neighbors(entity).next().is_some() {
+            return;
+        }
+
+        // SAFETY: Roots are unique and `propagate_recursive_of` only fetches descendants of
+        // `entity`, which can't be roots.
+        let Ok((_, global_transform)) = (unsafe { transform_query.get_unchecked(entity) }) else {
+            return;
+        };
+        let changed = global_transform.is_changed();
+
+        for child in graph.neighbors(entity) {
+            // SAFETY: `propagate_recursive_of` panics before fetching an entity that is
+            // targeted by more than one foster, so the relation is a forest below `entity`
+            // and other roots' calls will not conflict with this one.
+            unsafe {
+                propagate_recursive_of(
+                    &global_transform,
+                    &graph,
+                    &edges_query,
+                    &transform_query,
+                    entity,
+                    child,
+                    changed,
+                );
+            }
+        }
+    });
+}
+
+/// Recursively propagates the transforms for `entity` and all of its targets of relation `R`.
+///
+/// # Panics
+///
+/// If `entity` or any of its descendants has a foster other than the one it was reached from.
+///
+/// # Safety
+///
+/// - While this function is running, `transform_query` must not have any fetches for `entity`,
+/// nor any of its descendants.
+/// - The caller must ensure that the relation leading to `entity` is a tree or a forest.
+unsafe fn propagate_recursive_of<R: Relation>(
+    parent: &GlobalTransform,
+    graph: &RelationGraph<R>,
+    edges_query: &Query<Ref<Edges>>,
+    transform_query: &Query<(Ref<Transform>, &mut GlobalTransform)>,
+    foster: Entity,
+    entity: Entity,
+    mut changed: bool,
+) {
+    let mut fosters = graph.in_neighbors(entity);
+    assert!(
+        fosters.next() == Some(foster) && fosters.next().is_none(),
+        "Malformed relation. Transforms can only be propagated through relations where each entity has at most one foster"
+    );
+
+    changed |= edges_query
+        .get(entity)
+        .map_or(false, |edges| edges.is_changed());
+
+    let global_matrix = {
+        // SAFETY: This call cannot create aliased mutable references. Each root is handled by
+        // one task and the above assertion ensures `entity` is only reachable from `foster`.
+        let Ok((transform, mut global_transform)) =
+            (unsafe { transform_query.get_unchecked(entity) })
+        else {
+            return;
+        };
+
+        changed |= transform.is_changed();
+        if changed {
+            *global_transform = parent.mul_transform(*transform);
+        }
+        *global_transform
+    };
+
+    for child in graph.neighbors(entity) {
+        // SAFETY: The caller guarantees that `transform_query` will not be fetched
+        // for any descendants of `entity`, so it is safe to call `propagate_recursive_of` for
+        // each target.
+        unsafe {
+            propagate_recursive_of(
+                &global_matrix,
+                graph,
+                edges_query,
+                transform_query,
+                entity,
+                child,
+                changed,
+            );
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use bevy_app::prelude::*;
     use bevy_ecs::prelude::*;
-    use bevy_ecs::system::CommandQueue;
+    use bevy_ecs::system::{Command, CommandQueue};
     use bevy_math::vec3;
     use bevy_tasks::{ComputeTaskPool, TaskPool};
 
     use crate::components::{GlobalTransform, Transform};
     use crate::systems::*;
-    use crate::TransformBundle;
+    use crate::{TransformBundle, TransformPlugin, TransformPropagationPlugin};
+    use bevy_ecs::component::TableStorage;
+    use bevy_ecs::relation::{Relation, Set, UnSet};
     use bevy_hierarchy::{BuildChildren, BuildWorldChildren, Children, Parent};
+    use std::marker::PhantomData;
 
     #[test]
     fn did_propagate() {
@@ -400,4 +528,191 @@ mod test {
 
         app.update();
     }
+
+    struct MountedOn;
+
+    impl Relation for MountedOn {
+        type Storage = TableStorage;
+    }
+
+    #[test]
+    fn did_propagate_relation() {
+        ComputeTaskPool::init(TaskPool::default);
+        let mut world = World::default();
+
+        let mut schedule = Schedule::new();
+        schedule.add_systems((
+            sync_simple_transforms,
+            propagate_transforms,
+            propagate_transforms_of::<MountedOn>
+                .after(sync_simple_transforms)
+                .after(propagate_transforms),
+        ));
+
+        let [tank, truck, turret, gun] = [
+            Transform::from_xyz(1.0, 0.0, 0.0),
+            Transform::from_xyz(0.0, 0.0, 5.0),
+            Transform::from_xyz(0.0, 2.0, 0.0),
+            Transform::from_xyz(0.0, 0.0, 3.0),
+        ]
+        .map(|transform| world.spawn(TransformBundle::from(transform)).id());
+
+        for (foster, target) in [(tank, turret), (turret, gun)] {
+            Set {
+                foster,
+                target,
+                relation: MountedOn,
+            }
+            .write(&mut world);
+        }
+        schedule.run(&mut world);
+
+        assert_eq!(
+            *world.get::<GlobalTransform>(gun).unwrap(),
+            GlobalTransform::from_xyz(1.0, 2.0, 3.0)
+        );
+
+        // Moving the root moves everything mounted on it.
+        world.get_mut::<Transform>(tank).unwrap().translation.x = 4.0;
+        schedule.run(&mut world);
+
+        assert_eq!(
+            *world.get::<GlobalTransform>(gun).unwrap(),
+            GlobalTransform::from_xyz(4.0, 2.0, 3.0)
+        );
+
+        // Remounting is picked up through the changed edges.
+        UnSet::<MountedOn> {
+            foster: tank,
+            target: turret,
+            _phantom: PhantomData,
+        }
+        .write(&mut world);
+        Set {
+            foster: truck,
+            target: turret,
+            relation: MountedOn,
+        }
+        .write(&mut world);
+        schedule.run(&mut world);
+
+        assert_eq!(
+            *world.get::<GlobalTransform>(gun).unwrap(),
+            GlobalTransform::from_xyz(0.0, 2.0, 8.0)
+        );
+
+        // Detached entities fall back to their own transform, and so do their targets.
+        UnSet::<MountedOn> {
+            foster: truck,
+            target: turret,
+            _phantom: PhantomData,
+        }
+        .write(&mut world);
+        schedule.run(&mut world);
+
+        assert_eq!(
+            *world.get::<GlobalTransform>(turret).unwrap(),
+            GlobalTransform::from_xyz(0.0, 2.0, 0.0)
+        );
+        assert_eq!(
+            *world.get::<GlobalTransform>(gun).unwrap(),
+            GlobalTransform::from_xyz(0.0, 2.0, 3.0)
+        );
+
+        UnSet::<MountedOn> {
+            foster: turret,
+            target: gun,
+            _phantom: PhantomData,
+        }
+        .write(&mut world);
+        schedule.run(&mut world);
+
+        assert_eq!(
+            *world.get::<GlobalTransform>(gun).unwrap(),
+            GlobalTransform::from_xyz(0.0, 0.0, 3.0)
+        );
+    }
+
+    struct Tows;
+
+    impl Relation for Tows {
+        type Storage = TableStorage;
+    }
+
+    #[test]
+    fn propagate_two_relations() {
+        ComputeTaskPool::init(TaskPool::default);
+        let mut app = App::new();
+        app.add_plugin(TransformPlugin)
+            .add_plugin(TransformPropagationPlugin::<MountedOn>::default())
+            .add_plugin(TransformPropagationPlugin::<Tows>::default());
+
+        let [truck, trailer, cargo] = [
+            Transform::from_xyz(1.0, 0.0, 0.0),
+            Transform::from_xyz(0.0, 0.0, -4.0),
+            Transform::from_xyz(0.0, 1.0, 0.0),
+        ]
+        .map(|transform| app.world.spawn(TransformBundle::from(transform)).id());
+
+        Set {
+            foster: truck,
+            target: trailer,
+            relation: Tows,
+        }
+        .write(&mut app.world);
+        app.update();
+
+        // Changing the `MountedOn` edges of a towed trailer keeps its towed transform.
+        Set {
+            foster: trailer,
+            target: cargo,
+            relation: MountedOn,
+        }
+        .write(&mut app.world);
+        app.update();
+
+        assert_eq!(
+            *app.world.get::<GlobalTransform>(trailer).unwrap(),
+            GlobalTransform::from_xyz(1.0, 0.0, -4.0)
+        );
+        assert_eq!(
+            *app.world.get::<GlobalTransform>(cargo).unwrap(),
+            GlobalTransform::from_xyz(1.0, 1.0, -4.0)
+        );
+
+        // Losing the last `MountedOn` foster still resets the cargo.
+        UnSet::<MountedOn> {
+            foster: trailer,
+            target: cargo,
+            _phantom: PhantomData,
+        }
+        .write(&mut app.world);
+        app.update();
+
+        assert_eq!(
+            *app.world.get::<GlobalTransform>(cargo).unwrap(),
+            GlobalTransform::from_xyz(0.0, 1.0, 0.0)
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn panic_when_relation_has_two_fosters() {
+        ComputeTaskPool::init(TaskPool::default);
+        let mut world = World::default();
+
+        let mut schedule = Schedule::new();
+        schedule.add_systems(propagate_transforms_of::<MountedOn>);
+
+        let [a, b, c] = [(); 3].map(|_| world.spawn(TransformBundle::IDENTITY).id());
+        for (foster, target) in [(a, c), (b, c)] {
+            Set {
+                foster,
+                target,
+                relation: MountedOn,
+            }
+            .write(&mut world);
+        }
+        schedule.run(&mut world);
+    }
 }